@@ -1,46 +1,227 @@
-use std::{fs::File, io::Write, path::Path};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 
-use crate::{all_file_path, component::TSXContent, is_tsx, to_stories_path, StoryBookContent};
+use crate::{
+    all_file_path,
+    component::{Component, TSXContent},
+    is_tsx, to_component_stories_path, to_stories_path,
+    tsconfig::TsConfig,
+    StoryBookContent,
+};
 
 #[derive(Parser)]
 pub struct Cli {
     #[clap(default_value = "src")]
     root: String,
+    /// how stories files are laid out when a file exports several components
+    #[clap(long, value_enum, default_value_t = StoriesLayout::PerFile)]
+    layout: StoriesLayout,
+    /// component story format of the generated stories
+    #[clap(long, value_enum, default_value_t = StoriesFormat::Csf2)]
+    format: StoriesFormat,
+    /// how function props are sampled, defaults to fn for csf3 and action for csf2
+    #[clap(long, value_enum)]
+    actions: Option<ActionStyle>,
+    /// leave optional props out of the sample args
+    #[clap(long)]
+    omit_optional: bool,
+    /// also write a story for every literal member of union props
+    #[clap(long)]
+    variants: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StoriesLayout {
+    /// one stories file per source file, other components become subcomponents
+    PerFile,
+    /// one stories file per component, named after the component
+    PerComponent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ActionStyle {
+    /// fn() from @storybook/test, for Storybook 8 and later
+    Fn,
+    /// action("KEY") from @storybook/addon-actions, for older Storybook
+    Action,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StoriesFormat {
+    /// StoryFn and Template.bind({}), for Storybook 6
+    Csf2,
+    /// Meta and StoryObj, for Storybook 7 and later
+    Csf3,
 }
 
 impl Cli {
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         Self::parse()
     }
     pub fn run(&self) -> Result<(), std::io::Error> {
         let path = Path::new(&self.root);
+        // tsconfig.json が読めなくても paths の別名を使わずに続ける
+        let tsconfig = match TsConfig::find(path) {
+            Ok(tsconfig) => tsconfig,
+            Err(err) => {
+                eprintln!("skipped {}", err);
+                None
+            }
+        };
+        for skipped in tsconfig.iter().flat_map(TsConfig::skipped) {
+            eprintln!("skipped {}", skipped);
+        }
+        // STORIES PATH -> source file, to find components with the same name in a directory
+        let mut written = HashMap::new();
         if path.is_file() {
-            return self.run_to_file(path);
+            return self.run_to_file(path, tsconfig.as_ref(), &mut written);
         }
         let files = all_file_path(path)?;
         files
             .into_iter()
             .filter(|path| is_tsx(path))
             .try_for_each(|path| {
-                self.run_to_file(&path)?;
+                self.run_to_file(&path, tsconfig.as_ref(), &mut written)?;
                 Ok(())
             })
     }
-    fn run_to_file(&self, path: &Path) -> Result<(), std::io::Error> {
-        let content = TSXContent::from_file(&path)?;
-        let Some(component) = content.to_component() else {
-            return Ok(());
+    fn run_to_file(
+        &self,
+        path: &Path,
+        tsconfig: Option<&TsConfig>,
+        written: &mut HashMap<PathBuf, PathBuf>,
+    ) -> Result<(), std::io::Error> {
+        let content = TSXContent::from_file(path)?.with_tsconfig(tsconfig.cloned());
+        let mut components = match content.read_components() {
+            Ok((components, skipped)) => {
+                // import されているファイルが読めなくても component は書く
+                for err in skipped {
+                    eprintln!("skipped {}", err);
+                }
+                components
+            }
+            Err(err) => {
+                eprintln!("skipped {}", err);
+                return Ok(());
+            }
         };
+        if components.is_empty() {
+            return Ok(());
+        }
+        // tsconfig.json の paths に別名があればそれで import する
+        let module = tsconfig
+            .and_then(|tsconfig| tsconfig.module_specifier(path))
+            .unwrap_or_else(|| format!("./{}", path.file_stem().unwrap().to_str().unwrap()));
+        match self.layout {
+            StoriesLayout::PerComponent => components.into_iter().try_for_each(|component| {
+                let stories_path = to_component_stories_path(path, &component.name);
+                // Button.tsx と Buttons.tsx が両方 Button を export している
+                if let Some(other) = written.get(&stories_path) {
+                    eprintln!(
+                        "skipped {} in {}: {} is already written for {}",
+                        component.name,
+                        path.display(),
+                        stories_path.display(),
+                        other.display()
+                    );
+                    return Ok(());
+                }
+                written.insert(stories_path.clone(), path.to_path_buf());
+                self.write_stories(&stories_path, &module, component, Vec::new())
+            }),
+            StoriesLayout::PerFile => {
+                let component = components.remove(0);
+                self.write_stories(&to_stories_path(path), &module, component, components)
+            }
+        }
+    }
+    fn write_stories(
+        &self,
+        stories_path: &Path,
+        module: &str,
+        component: Component,
+        subcomponents: Vec<Component>,
+    ) -> Result<(), std::io::Error> {
         let storybook =
-            StoryBookContent::new(format!("Example/{}", component.name.as_str()), component);
-        let mut file = File::create(to_stories_path(path)).unwrap();
-        file.write_all(
-            storybook
-                .to_file_content(path.file_stem().unwrap().to_str().unwrap())
-                .as_bytes(),
-        )?;
+            StoryBookContent::new(format!("Example/{}", component.name.as_str()), component)
+                .with_subcomponents(subcomponents)
+                .with_format(self.format)
+                .with_variants(self.variants)
+                .with_actions(self.actions)
+                .with_omit_optional(self.omit_optional);
+        let mut file = File::create(stories_path)?;
+        file.write_all(storybook.to_file_content(module).as_bytes())?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::{create_dir_all, create_file, remove_dir};
+
+    #[test]
+    fn test_default_layout() {
+        let cli = Cli::parse_from(["storybook-helper"]);
+        assert_eq!(cli.layout, StoriesLayout::PerFile);
+    }
+    #[test]
+    fn test_per_component_name_collision() {
+        let dir_name = "test_per_component_name_collision";
+        std::fs::create_dir_all(dir_name).unwrap();
+        let button = Path::new(dir_name).join("Button.tsx");
+        let other = Path::new(dir_name).join("Other.tsx");
+        std::fs::write(
+            &button,
+            "export const Button = (props: { label: string }) => <button />;",
+        )
+        .unwrap();
+        std::fs::write(
+            &other,
+            "export const Button = (props: { caption: string }) => <button />;",
+        )
+        .unwrap();
+
+        let cli = Cli::parse_from(["storybook-helper", dir_name, "--layout", "per-component"]);
+        let mut written = HashMap::new();
+        let first = cli.run_to_file(&button, None, &mut written);
+        let second = cli.run_to_file(&other, None, &mut written);
+        let stories = std::fs::read_to_string(Path::new(dir_name).join("Button.stories.tsx"));
+        std::fs::remove_dir_all(dir_name).unwrap();
+
+        assert!(first.is_ok() && second.is_ok());
+        // 2つ目の Button は上書きせずに報告だけする
+        let stories = stories.unwrap();
+        assert!(stories.contains("label"));
+        assert!(!stories.contains("caption"));
+    }
+    #[test]
+    fn test_run_with_unresolvable_tsconfig() {
+        let dir_name = "test_run_with_unresolvable_tsconfig";
+        create_dir_all(dir_name);
+        create_file(
+            format!("{dir_name}/tsconfig.json").as_str(),
+            r#"{ "extends": "@vue/tsconfig/tsconfig.dom.json" }"#,
+        );
+        create_file(
+            format!("{dir_name}/Button.tsx").as_str(),
+            "export const Button = (props: { label: string }) => <button />;",
+        );
+
+        let cli = Cli::parse_from(["storybook-helper", dir_name]);
+        let result = cli.run();
+        let stories = std::fs::read_to_string(format!("{dir_name}/Button.stories.tsx"));
+        remove_dir(dir_name);
+
+        // extends が読めなくても stories は書く
+        assert!(result.is_ok());
+        assert!(stories.unwrap().contains("label"));
+    }
+}
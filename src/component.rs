@@ -1,17 +1,64 @@
-use std::{collections::BTreeMap, path::Path};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 
-use crate::parser::ComponentPartsParser;
+use crate::{
+    cli::ActionStyle,
+    parser::{ComponentPartsParser, ParseError},
+    symbol::Import,
+    token::Span,
+    tsconfig::TsConfig,
+};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub(super) struct Component {
     pub name: String,
     props: Props,
+    // where the component name is declared
+    span: Option<Span>,
+    is_default_export: bool,
 }
 impl Component {
     pub fn new(name: impl Into<String>, props: Props) -> Self {
         Self {
             name: name.into(),
             props,
+            span: None,
+            is_default_export: false,
+        }
+    }
+    // the type arguments of Props<T> are written on the component, not on the declaration
+    pub fn with_props(mut self, props: Props) -> Self {
+        self.props = match (self.props, props) {
+            (Props::Named(current), Props::Named(props)) => {
+                Props::Named(props.with_args(current.args))
+            }
+            (_, props) => props,
+        };
+        self
+    }
+    pub fn default_export(mut self) -> Self {
+        self.is_default_export = true;
+        self
+    }
+    pub fn is_default_export(&self) -> bool {
+        self.is_default_export
+    }
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+    #[cfg(test)]
+    pub fn span(&self) -> Option<Span> {
+        self.span
+    }
+    // where the named props type is declared
+    #[cfg(test)]
+    pub fn props_span(&self) -> Option<Span> {
+        match &self.props {
+            Props::Named(props) => props.span,
+            Props::Expand(_) => None,
         }
     }
     pub fn props_name(&self) -> Option<&str> {
@@ -20,35 +67,151 @@ impl Component {
             Props::Expand(_) => None,
         }
     }
+    // (MODULE, SPECIFIER) of import { SPECIFIER } from "MODULE"
+    // MODULE is None when the props type is declared in the component file
+    pub fn props_import(&self) -> Option<(Option<&str>, String)> {
+        match &self.props {
+            Props::Named(props) => Some((props.import_module(), props.import_specifier())),
+            Props::Expand(_) => None,
+        }
+    }
     pub fn props_str(&self) -> String {
         match &self.props {
-            Props::Named(props) => props.name.clone(),
+            Props::Named(props) => props.name_with_args(),
             Props::Expand(props) => props.to_str(),
         }
     }
+    #[cfg(test)]
     pub fn expand_str(&self) -> String {
         match &self.props {
             Props::Named(props) => props.expand_str(),
             Props::Expand(props) => props.to_str(),
         }
     }
-    pub fn fill_sample(&self) -> String {
+    pub fn fill_sample(&self, options: &SampleOptions) -> String {
+        match &self.props {
+            Props::Named(props) => props.inner.sample("", options),
+            Props::Expand(props) => props.fill_sample(options),
+        }
+    }
+    // whether fill_sample uses fn() or action()
+    pub fn has_function_props(&self, options: &SampleOptions) -> bool {
+        match &self.props {
+            Props::Named(props) => props.inner.contains_function(options),
+            Props::Expand(props) => props.contains_function(options),
+        }
+    }
+    // (KEY, ArgType) for props Storybook should show a control for
+    pub fn arg_types(&self) -> Vec<(String, ArgType)> {
+        match &self.props {
+            Props::Named(props) => props.inner.arg_types(),
+            Props::Expand(props) => props.arg_types(),
+        }
+    }
+    // (MODULE, SPECIFIER) of enums the samples refer to, such as ButtonSize.Small
+    // MODULE is None when the enum is declared in the component file
+    pub fn enum_imports(&self) -> Vec<(Option<&str>, String)> {
+        let enums = match &self.props {
+            Props::Named(props) => props.inner.enums(),
+            Props::Expand(props) => props.enums(),
+        };
+        let mut imports: Vec<(Option<&str>, String)> = Vec::new();
+        for enum_type in enums {
+            let import = (enum_type.import_module(), enum_type.import_specifier());
+            if !imports.contains(&import) {
+                imports.push(import);
+            }
+        }
+        imports
+    }
+    // replaces the props type with what evaluate makes of it
+    // interface Props extends Omit<Base, "onClick"> {} -> { KEY: TYPE }
+    pub fn evaluate_props<E>(
+        &mut self,
+        mut evaluate: impl FnMut(&Type) -> Result<Type, E>,
+    ) -> Result<(), E> {
+        match &mut self.props {
+            Props::Named(props) => *props.inner = evaluate(&props.inner)?,
+            Props::Expand(props) => {
+                if let Type::Object(evaluated) = evaluate(&Type::Object(props.clone()))? {
+                    *props = evaluated;
+                }
+            }
+        }
+        Ok(())
+    }
+    // replaces NAME in the props with what resolve finds for it
+    pub fn resolve_aliases<E>(
+        &mut self,
+        resolve: &mut impl FnMut(&str) -> Result<Option<Type>, E>,
+    ) -> Result<(), E> {
+        match &mut self.props {
+            Props::Named(props) => props.inner.resolve_aliases(resolve),
+            Props::Expand(props) => props.resolve_aliases(resolve),
+        }
+    }
+    // (KEY, LITERAL) of union props like variant: "primary" | "secondary"
+    // the first member is already used by fill_sample
+    pub fn variants(&self) -> Vec<(String, String)> {
         match &self.props {
-            Props::Named(props) => props.inner.sample(),
-            Props::Expand(props) => props.fill_sample(),
+            Props::Named(props) => props.inner.variants(),
+            Props::Expand(props) => props.variants(),
         }
     }
 }
-pub(crate) struct TSXContent(pub String);
+// span is where the component was found, not what the component is
+impl PartialEq for Component {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.props == other.props
+            && self.is_default_export == other.is_default_export
+    }
+}
+pub(crate) struct TSXContent {
+    source: String,
+    path: Option<PathBuf>,
+    // used to resolve aliased imports such as @/types
+    tsconfig: Option<TsConfig>,
+}
 
 impl TSXContent {
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            path: None,
+            tsconfig: None,
+        }
+    }
+    pub fn with_tsconfig(mut self, tsconfig: Option<TsConfig>) -> Self {
+        self.tsconfig = tsconfig;
+        self
+    }
     pub fn from_file(path: impl AsRef<Path>) -> Result<Self, std::io::Error> {
-        let content = std::fs::read_to_string(path)?;
-        Ok(Self(content))
+        let source = std::fs::read_to_string(&path)?;
+        let mut content = Self::new(source);
+        content.path = Some(path.as_ref().to_path_buf());
+        Ok(content)
+    }
+    pub fn source(&self) -> &str {
+        &self.source
     }
-    pub fn to_component(&self) -> Option<Component> {
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+    pub fn tsconfig(&self) -> Option<&TsConfig> {
+        self.tsconfig.as_ref()
+    }
+    // the components and the errors of imported files that could not be parsed
+    pub fn read_components(&self) -> Result<(Vec<Component>, Vec<ParseError>), ParseError> {
         let mut parser = ComponentPartsParser::new(self);
-        parser.search_component()
+        parser.search_components().map_err(|err| match &self.path {
+            Some(path) => err.with_file(path),
+            None => err,
+        })
+    }
+    #[cfg(test)]
+    pub fn to_components(&self) -> Result<Vec<Component>, ParseError> {
+        self.read_components().map(|(components, _)| components)
     }
 }
 
@@ -58,39 +221,120 @@ pub(super) enum Props {
     Expand(ObjectType),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub(super) struct NamedProps {
     pub name: String,
-    inner: Type,
+    // TYPE of Props<TYPE>
+    args: Vec<Type>,
+    inner: Box<Type>,
+    // where the props type is declared
+    span: Option<Span>,
+    // set when the props type is imported into the component file
+    import: Option<Import>,
 }
 
 impl NamedProps {
     pub fn new(name: impl Into<String>, inner: ObjectType) -> Self {
         Self {
             name: name.into(),
-            inner: Type::Object(inner),
+            args: Vec::new(),
+            inner: Box::new(Type::Object(inner)),
+            span: None,
+            import: None,
         }
     }
+    #[cfg(test)]
     pub fn new_object_type(name: impl Into<String>, inner: ObjectType) -> Self {
         Self {
             name: name.into(),
-            inner: Type::Object(inner),
+            args: Vec::new(),
+            inner: Box::new(Type::Object(inner)),
+            span: None,
+            import: None,
+        }
+    }
+    // type NAME = TYPE
+    pub fn new_type(name: impl Into<String>, inner: Type) -> Self {
+        Self {
+            name: name.into(),
+            args: Vec::new(),
+            inner: Box::new(inner),
+            span: None,
+            import: None,
         }
     }
     pub fn new_intersection_type(name: impl Into<String>, inner: Vec<Type>) -> Self {
         Self {
             name: name.into(),
-            inner: Type::Intersection(inner),
+            args: Vec::new(),
+            inner: Box::new(Type::Intersection(inner)),
+            span: None,
+            import: None,
+        }
+    }
+    // import { NAME as LOCAL } from "MODULE" された型として扱う
+    pub fn imported(mut self, local: impl Into<String>, import: Import) -> Self {
+        self.name = local.into();
+        self.import = Some(import);
+        self
+    }
+    pub fn with_args(mut self, args: Vec<Type>) -> Self {
+        self.args = args;
+        self
+    }
+    // Props or Props<string, unknown>
+    pub fn name_with_args(&self) -> String {
+        if self.args.is_empty() {
+            return self.name.clone();
         }
+        format!(
+            "{}<{}>",
+            self.name,
+            self.args
+                .iter()
+                .map(|ty| ty.to_str())
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
     }
+    pub fn inner(&self) -> &Type {
+        &self.inner
+    }
+    // module to import the props type from, None is the component file
+    pub fn import_module(&self) -> Option<&str> {
+        self.import.as_ref().map(|import| import.module.as_str())
+    }
+    // NAME or NAME as LOCAL
+    pub fn import_specifier(&self) -> String {
+        match &self.import {
+            Some(import) if import.name != self.name => {
+                format!("{} as {}", import.name, self.name)
+            }
+            _ => self.name.clone(),
+        }
+    }
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+    #[cfg(test)]
     pub fn expand_str(&self) -> String {
         self.inner.to_str()
     }
 }
+// span is where the props type was found, not what the props type is
+impl PartialEq for NamedProps {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.args == other.args
+            && self.inner == other.inner
+            && self.import == other.import
+    }
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub(super) struct ObjectType {
-    inner: BTreeMap<Key, Type>,
+    inner: BTreeMap<Key, Property>,
 }
 
 impl ObjectType {
@@ -99,28 +343,198 @@ impl ObjectType {
             inner: BTreeMap::new(),
         }
     }
+    #[cfg(test)]
     pub fn insert(&mut self, key: Key, ty: Type) {
-        self.inner.insert(key, ty);
+        self.inner.insert(key, Property::new(ty));
+    }
+    pub fn insert_property(&mut self, key: Key, property: Property) {
+        self.inner.insert(key, property);
+    }
+    pub fn properties(&self) -> impl Iterator<Item = (&Key, &Property)> {
+        self.inner.iter()
+    }
+    pub fn get(&self, key: &str) -> Option<&Property> {
+        self.inner.get(&Key(key.to_string()))
+    }
+    // A & B, properties of other win
+    pub fn merge(mut self, other: ObjectType) -> Self {
+        self.inner.extend(other.inner);
+        self
+    }
+    // Partial<T>, Required<T>, Readonly<T>
+    pub fn map_properties(&self, f: impl Fn(Property) -> Property) -> Self {
+        Self {
+            inner: self
+                .inner
+                .iter()
+                .map(|(key, property)| (key.clone(), f(property.clone())))
+                .collect(),
+        }
+    }
+    // Pick<T, K>, Omit<T, K>
+    pub fn retain(&self, mut f: impl FnMut(&Key) -> bool) -> Self {
+        let mut props = self.clone();
+        props.inner.retain(|key, _| f(key));
+        props
     }
     fn to_str(&self) -> String {
         let mut props = String::new();
-        for (key, ty) in &self.inner {
-            props.push_str(&format!("{}: {},", key.0, ty.to_str()));
+        for (key, property) in &self.inner {
+            props.push_str(&format!("{},", property.to_str(key)));
         }
         format!("{{ {} }}", props)
     }
-    fn fill_sample(&self) -> String {
+    fn fill_sample(&self, options: &SampleOptions) -> String {
         let mut props = String::new();
-        for (key, ty) in &self.inner {
-            props.push_str(&format!("{}: {},", key.0, ty.sample()));
+        for (key, property) in self.sampled_properties(options) {
+            // nested objects are sampled as nested object literals
+            props.push_str(&format!(
+                "{}: {},",
+                key.property(),
+                property.ty.sample(&key.0, options)
+            ));
         }
         format!("{{ {} }}", props)
     }
+    fn sampled_properties<'a>(
+        &'a self,
+        options: &'a SampleOptions,
+    ) -> impl Iterator<Item = (&'a Key, &'a Property)> {
+        self.inner
+            .iter()
+            .filter(|(_, property)| !(property.optional && options.omit_optional))
+    }
+    fn contains_function(&self, options: &SampleOptions) -> bool {
+        self.sampled_properties(options)
+            .any(|(_, property)| property.ty.contains_function(options))
+    }
+    fn arg_types(&self) -> Vec<(String, ArgType)> {
+        self.inner
+            .iter()
+            .filter_map(|(key, property)| Some((key.0.clone(), property.ty.arg_type()?)))
+            .collect()
+    }
+    fn enums(&self) -> Vec<&EnumType> {
+        self.inner
+            .values()
+            .flat_map(|property| property.ty.enums())
+            .collect()
+    }
+    fn resolve_aliases<E>(
+        &mut self,
+        resolve: &mut impl FnMut(&str) -> Result<Option<Type>, E>,
+    ) -> Result<(), E> {
+        for property in self.inner.values_mut() {
+            property.ty.resolve_aliases(resolve)?;
+        }
+        Ok(())
+    }
+    fn variants(&self) -> Vec<(String, String)> {
+        let mut variants = Vec::new();
+        for (key, property) in &self.inner {
+            let Type::Union(tys) = &property.ty else {
+                continue;
+            };
+            let sampled = Type::sampled_member(tys);
+            for ty in tys.iter().filter(|ty| Some(*ty) != sampled) {
+                match ty {
+                    Type::Literal(literal) => variants.push((key.0.clone(), literal.sample())),
+                    // string | null -> Null story
+                    Type::Primitive(primitive) if primitive.is_nullish() => {
+                        variants.push((key.0.clone(), primitive.to_str()))
+                    }
+                    _ => {}
+                }
+            }
+        }
+        variants
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Hash, PartialOrd, Ord, Eq)]
 pub(super) struct Key(pub String);
 
+impl Key {
+    // name as written in an object literal, data-id -> "data-id"
+    pub fn property(&self) -> String {
+        property_key(&self.0)
+    }
+}
+
+// readonly KEY?: TYPE
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Property {
+    pub ty: Type,
+    optional: bool,
+    readonly: bool,
+}
+
+impl Property {
+    pub fn new(ty: Type) -> Self {
+        Self {
+            ty,
+            optional: false,
+            readonly: false,
+        }
+    }
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+    pub fn readonly(mut self) -> Self {
+        self.readonly = true;
+        self
+    }
+    pub fn required(mut self) -> Self {
+        self.optional = false;
+        self
+    }
+    // ? and readonly only belong to the type, not to samples
+    fn to_str(&self, key: &Key) -> String {
+        format!(
+            "{}{}{}: {}",
+            if self.readonly { "readonly " } else { "" },
+            key.property(),
+            if self.optional { "?" } else { "" },
+            self.ty.to_str()
+        )
+    }
+}
+
+// how samples of props are written
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct SampleOptions {
+    pub actions: ActionStyle,
+    // leave optional props out of samples
+    pub omit_optional: bool,
+}
+
+impl SampleOptions {
+    pub fn new(actions: ActionStyle) -> Self {
+        Self {
+            actions,
+            omit_optional: false,
+        }
+    }
+    pub fn with_omit_optional(mut self, omit_optional: bool) -> Self {
+        self.omit_optional = omit_optional;
+        self
+    }
+}
+
+// aria-label -> "aria-label"
+pub(super) fn property_key(key: &str) -> String {
+    if key.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '$')
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+    {
+        key.to_string()
+    } else {
+        format!("\"{}\"", key)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub(super) enum Type {
     Primitive(PrimitiveType),
@@ -128,8 +542,22 @@ pub(super) enum Type {
     Alias(String),
     Union(Vec<Type>),
     Intersection(Vec<Type>),
-    Literal(String),
+    Literal(LiteralType),
     Array(Box<Type>),
+    // (PARAMS) => RET
+    Function { params: Vec<Param>, ret: Box<Type> },
+    // NAME<ARGS>
+    Generic { name: String, args: Vec<Type> },
+    // [TYPE, LABEL?: TYPE, ...TYPE[]]
+    Tuple(Vec<TupleElement>),
+    // enum NAME { MEMBER, MEMBER }
+    Enum(EnumType),
+    // keyof TYPE
+    Keyof(Box<Type>),
+    // typeof NAME.NAME, the type of a value that is not read
+    Typeof(String),
+    // TYPE[INDEX]
+    Indexed { object: Box<Type>, index: Box<Type> },
 }
 impl Type {
     pub fn to_str(&self) -> String {
@@ -139,35 +567,608 @@ impl Type {
             Self::Alias(s) => s.clone(),
             Self::Union(tys) => tys
                 .iter()
-                .map(|ty| ty.to_str())
+                .map(|ty| ty.to_str_within(1))
                 .collect::<Vec<String>>()
                 .join(" | "),
             Self::Intersection(tys) => tys
                 .iter()
-                .map(|ty| ty.to_str())
+                .map(|ty| ty.to_str_within(2))
                 .collect::<Vec<String>>()
                 .join(" & "),
-            Self::Literal(s) => s.clone(),
-            Self::Array(ty) => format!("{}[]", ty.to_str()),
+            Self::Literal(literal) => literal.to_str(),
+            Self::Array(ty) => format!("{}[]", ty.to_str_within(4)),
+            Self::Function { params, ret } => format!(
+                "({}) => {}",
+                params
+                    .iter()
+                    .map(|param| param.to_str())
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                ret.to_str()
+            ),
+            Self::Generic { name, args } => format!(
+                "{}<{}>",
+                name,
+                args.iter()
+                    .map(|ty| ty.to_str())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            Self::Tuple(elements) => format!(
+                "[{}]",
+                elements
+                    .iter()
+                    .map(|element| element.to_str())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            Self::Enum(enum_type) => enum_type.name.clone(),
+            Self::Keyof(ty) => format!("keyof {}", ty.to_str_within(3)),
+            Self::Typeof(name) => format!("typeof {}", name),
+            Self::Indexed { object, index } => {
+                format!("{}[{}]", object.to_str_within(4), index.to_str())
+            }
         }
     }
-    fn sample(&self) -> String {
+    // how loosely the type binds, () => void < A | B < A & B < keyof A < A[]
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Function { .. } => 0,
+            Self::Union(_) => 1,
+            Self::Intersection(_) => 2,
+            Self::Keyof(_) => 3,
+            _ => 4,
+        }
+    }
+    // (A | B)[] and (() => void) | null keep their meaning with the ()
+    fn to_str_within(&self, precedence: u8) -> String {
+        if self.precedence() < precedence {
+            format!("({})", self.to_str())
+        } else {
+            self.to_str()
+        }
+    }
+    // key is the prop the sample is for, used to name actions
+    fn sample(&self, key: &str, options: &SampleOptions) -> String {
         match self {
             Self::Primitive(ty) => ty.sample(),
-            Self::Object(props) => props.fill_sample(),
-            Self::Alias(s) => s.clone(),
-            Self::Union(tys) => tys
+            Self::Object(props) => props.fill_sample(options),
+            // still a reference after evaluating, such as T or an import that can not be read
+            Self::Alias(_) | Self::Keyof(_) | Self::Typeof(_) | Self::Indexed { .. } => {
+                "undefined".to_string()
+            }
+            // "a" | "b" is not a value, the first member is
+            Self::Union(tys) => Self::sampled_member(tys)
+                .map(|ty| ty.sample(key, options))
+                .unwrap_or_default(),
+            // { a } & { b } -> { a, b }, members that are not objects can not be sampled
+            Self::Intersection(tys) => {
+                let mut objects = tys.iter().filter_map(|ty| match ty {
+                    Self::Object(props) => Some(props.clone()),
+                    _ => None,
+                });
+                match objects.next() {
+                    Some(first) => objects.fold(first, ObjectType::merge).fill_sample(options),
+                    None => "undefined".to_string(),
+                }
+            }
+            Self::Literal(literal) => literal.sample(),
+            Self::Array(ty) => format!("[{}]", ty.sample(key, options)),
+            Self::Function { .. } => match options.actions {
+                ActionStyle::Fn => "fn()".to_string(),
+                ActionStyle::Action => format!(r#"action("{}")"#, key),
+            },
+            Self::Generic { name, args } => match (name.as_str(), args.as_slice()) {
+                ("Array" | "ReadonlyArray", [ty]) => format!("[{}]", ty.sample(key, options)),
+                ("Promise", [Self::Primitive(PrimitiveType::Void)]) => {
+                    "Promise.resolve()".to_string()
+                }
+                ("Promise", [ty]) => format!("Promise.resolve({})", ty.sample(key, options)),
+                // Record<"a" | "b", V> -> { "a": V, "b": V, }
+                ("Record", [keys, value]) => {
+                    let keys = match keys {
+                        Self::Literal(key) => vec![key],
+                        Self::Union(keys) => keys
+                            .iter()
+                            .filter_map(|key| match key {
+                                Self::Literal(key) => Some(key),
+                                _ => None,
+                            })
+                            .collect(),
+                        _ => Vec::new(),
+                    };
+                    let mut props = String::new();
+                    for key in keys {
+                        props.push_str(&format!(
+                            "{}: {},",
+                            key.sample(),
+                            value.sample(&key.value(), options)
+                        ));
+                    }
+                    format!("{{ {} }}", props)
+                }
+                // Map<K, V> や React.Dispatch<...> は型であって値ではない
+                _ => "undefined".to_string(),
+            },
+            // one value per element
+            Self::Tuple(elements) => format!(
+                "[{}]",
+                elements
+                    .iter()
+                    .filter(|element| element.is_sampled(options))
+                    .map(|element| element.ty.sample(key, options))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            Self::Enum(enum_type) => enum_type
+                .values()
+                .into_iter()
+                .next()
+                .unwrap_or_else(|| enum_type.name.clone()),
+        }
+    }
+    fn contains_function(&self, options: &SampleOptions) -> bool {
+        match self {
+            Self::Function { .. } => true,
+            Self::Object(props) => props.contains_function(options),
+            Self::Union(tys) => {
+                Self::sampled_member(tys).is_some_and(|ty| ty.contains_function(options))
+            }
+            Self::Intersection(tys) => tys.iter().any(|ty| ty.contains_function(options)),
+            Self::Array(ty) => ty.contains_function(options),
+            Self::Tuple(elements) => elements
                 .iter()
-                .map(|ty| ty.sample())
-                .collect::<Vec<String>>()
-                .join(" | "),
-            Self::Intersection(tys) => tys
+                .filter(|element| element.is_sampled(options))
+                .any(|element| element.ty.contains_function(options)),
+            Self::Generic { name, args } => {
+                matches!(name.as_str(), "Array" | "ReadonlyArray" | "Record")
+                    && args.last().is_some_and(|ty| ty.contains_function(options))
+            }
+            _ => false,
+        }
+    }
+    fn arg_types(&self) -> Vec<(String, ArgType)> {
+        match self {
+            Self::Object(props) => props.arg_types(),
+            Self::Intersection(tys) => tys.iter().flat_map(|ty| ty.arg_types()).collect(),
+            _ => Vec::new(),
+        }
+    }
+    // control of the prop typed by self
+    fn arg_type(&self) -> Option<ArgType> {
+        match self {
+            Self::Primitive(PrimitiveType::Boolean) => Some(ArgType::Boolean),
+            Self::Primitive(PrimitiveType::Number) => Some(ArgType::Number),
+            Self::Object(_) | Self::Array(_) | Self::Tuple(_) => Some(ArgType::Object),
+            Self::Generic { name, .. }
+                if matches!(name.as_str(), "Array" | "ReadonlyArray" | "Record") =>
+            {
+                Some(ArgType::Object)
+            }
+            Self::Function { .. } => Some(ArgType::Action),
+            Self::Enum(enum_type) => Some(ArgType::Select(enum_type.values())),
+            Self::Union(tys) => {
+                // variant?: "a" | "b" | undefined
+                let tys = tys.iter().filter(|ty| !ty.is_nullish()).collect::<Vec<_>>();
+                if let [ty] = tys.as_slice() {
+                    return ty.arg_type();
+                }
+                // true | false は boolean と同じ
+                if tys
+                    .iter()
+                    .all(|ty| matches!(ty, Self::Literal(LiteralType::Boolean(_))))
+                {
+                    return Some(ArgType::Boolean);
+                }
+                tys.iter()
+                    .map(|ty| match ty {
+                        Self::Literal(literal) => Some(literal.sample()),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>()
+                    .map(ArgType::options)
+            }
+            _ => None,
+        }
+    }
+    // types written directly inside self
+    fn children(&self) -> Vec<&Type> {
+        match self {
+            Self::Object(props) => props.inner.values().map(|property| &property.ty).collect(),
+            Self::Union(tys) | Self::Intersection(tys) => tys.iter().collect(),
+            Self::Array(ty) => vec![ty],
+            Self::Function { params, ret } => params
                 .iter()
-                .map(|ty| ty.sample())
-                .collect::<Vec<String>>()
-                .join(" & "),
-            Self::Literal(s) => s.clone(),
-            Self::Array(ty) => format!("[{}]", ty.sample()),
+                .filter_map(|param| param.ty.as_ref())
+                .chain(std::iter::once(ret.as_ref()))
+                .collect(),
+            Self::Generic { args, .. } => args.iter().collect(),
+            Self::Tuple(elements) => elements.iter().map(|element| &element.ty).collect(),
+            Self::Keyof(ty) => vec![ty],
+            Self::Indexed { object, index } => vec![object, index],
+            Self::Literal(LiteralType::Template { placeholders, .. }) => {
+                placeholders.iter().collect()
+            }
+            Self::Primitive(_)
+            | Self::Alias(_)
+            | Self::Literal(_)
+            | Self::Enum(_)
+            | Self::Typeof(_) => Vec::new(),
+        }
+    }
+    fn children_mut(&mut self) -> Vec<&mut Type> {
+        match self {
+            Self::Object(props) => props
+                .inner
+                .values_mut()
+                .map(|property| &mut property.ty)
+                .collect(),
+            Self::Union(tys) | Self::Intersection(tys) => tys.iter_mut().collect(),
+            Self::Array(ty) => vec![ty],
+            Self::Function { params, ret } => params
+                .iter_mut()
+                .filter_map(|param| param.ty.as_mut())
+                .chain(std::iter::once(ret.as_mut()))
+                .collect(),
+            Self::Generic { args, .. } => args.iter_mut().collect(),
+            Self::Tuple(elements) => elements.iter_mut().map(|element| &mut element.ty).collect(),
+            Self::Keyof(ty) => vec![ty],
+            Self::Indexed { object, index } => vec![object, index],
+            Self::Literal(LiteralType::Template { placeholders, .. }) => {
+                placeholders.iter_mut().collect()
+            }
+            Self::Primitive(_)
+            | Self::Alias(_)
+            | Self::Literal(_)
+            | Self::Enum(_)
+            | Self::Typeof(_) => Vec::new(),
+        }
+    }
+    fn enums(&self) -> Vec<&EnumType> {
+        match self {
+            Self::Enum(enum_type) => vec![enum_type],
+            _ => self
+                .children()
+                .into_iter()
+                .flat_map(|ty| ty.enums())
+                .collect(),
+        }
+    }
+    // NAME -> what resolve finds, kept as is when nothing is found
+    pub fn resolve_aliases<E>(
+        &mut self,
+        resolve: &mut impl FnMut(&str) -> Result<Option<Type>, E>,
+    ) -> Result<(), E> {
+        if let Self::Alias(name) = self {
+            if let Some(ty) = resolve(name)? {
+                *self = ty;
+            }
+            return Ok(());
+        }
+        for ty in self.children_mut() {
+            ty.resolve_aliases(resolve)?;
+        }
+        Ok(())
+    }
+    // string | null samples the string, not null
+    fn sampled_member(tys: &[Type]) -> Option<&Type> {
+        tys.iter().find(|ty| !ty.is_nullish()).or(tys.first())
+    }
+    fn is_nullish(&self) -> bool {
+        matches!(self, Self::Primitive(ty) if ty.is_nullish())
+    }
+    fn variants(&self) -> Vec<(String, String)> {
+        match self {
+            Self::Object(props) => props.variants(),
+            Self::Intersection(tys) => tys.iter().flat_map(|ty| ty.variants()).collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+// NAME?: TYPE or ...NAME: TYPE of a function type
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Param {
+    pub name: String,
+    // None when the type is omitted, (a) => void
+    pub ty: Option<Type>,
+    optional: bool,
+    rest: bool,
+}
+
+impl Param {
+    pub fn new(name: impl Into<String>, ty: Option<Type>) -> Self {
+        Self {
+            name: name.into(),
+            ty,
+            optional: false,
+            rest: false,
+        }
+    }
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+    pub fn rest(mut self) -> Self {
+        self.rest = true;
+        self
+    }
+    pub fn to_str(&self) -> String {
+        let mut param = String::new();
+        if self.rest {
+            param.push_str("...");
+        }
+        param.push_str(&self.name);
+        if self.optional {
+            param.push('?');
+        }
+        if let Some(ty) = &self.ty {
+            param.push_str(": ");
+            param.push_str(&ty.to_str());
+        }
+        param
+    }
+}
+
+// "a", 'a', 1, true or `a-${string}`, kept as written in the source
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum LiteralType {
+    // with the quotes
+    String(String),
+    Number(String),
+    Boolean(bool),
+    // with the backquotes, and the text around and the types of the ${} placeholders
+    Template {
+        raw: String,
+        quasis: Vec<String>,
+        placeholders: Vec<Type>,
+    },
+}
+
+impl LiteralType {
+    pub fn to_str(&self) -> String {
+        match self {
+            Self::String(s) | Self::Number(s) | Self::Template { raw: s, .. } => s.clone(),
+            Self::Boolean(b) => b.to_string(),
+        }
+    }
+    // `a-${number}` is not a value, `a-0` is
+    fn sample(&self) -> String {
+        match self {
+            Self::Template {
+                quasis,
+                placeholders,
+                ..
+            } => {
+                let mut sample = String::from("`");
+                for (i, quasi) in quasis.iter().enumerate() {
+                    sample.push_str(quasi);
+                    if let Some(placeholder) = placeholders.get(i) {
+                        sample.push_str(&Self::placeholder_sample(placeholder));
+                    }
+                }
+                sample.push('`');
+                sample
+            }
+            _ => self.to_str(),
+        }
+    }
+    // ${number} -> 0, ${string} -> "", ${"sm" | "lg"} -> sm
+    fn placeholder_sample(ty: &Type) -> String {
+        match ty {
+            Type::Literal(literal @ Self::Template { .. }) => {
+                let sample = literal.sample();
+                sample[1..sample.len() - 1].to_string()
+            }
+            Type::Literal(literal) => literal.value(),
+            Type::Primitive(PrimitiveType::Number | PrimitiveType::BigInt) => "0".to_string(),
+            Type::Primitive(PrimitiveType::Boolean) => "false".to_string(),
+            Type::Union(tys) => tys
+                .iter()
+                .find(|ty| !ty.is_nullish())
+                .map(Self::placeholder_sample)
+                .unwrap_or_default(),
+            // string and types that are not known here
+            _ => String::new(),
+        }
+    }
+    // "a" -> a, 0xff -> 255, used as keys and story names
+    pub fn value(&self) -> String {
+        match self {
+            Self::String(s) | Self::Template { raw: s, .. } => {
+                let mut chars = s.chars();
+                chars.next();
+                chars.next_back();
+                chars.as_str().to_string()
+            }
+            Self::Number(_) => self
+                .number()
+                .map(|number| number.to_string())
+                .unwrap_or_else(|| self.to_str()),
+            Self::Boolean(_) => self.to_str(),
+        }
+    }
+    // -1, 1_000, 0xff, 1.5e3 and 10n as a number, None for other literals
+    pub fn number(&self) -> Option<f64> {
+        let Self::Number(s) = self else {
+            return None;
+        };
+        let (sign, s) = match s.strip_prefix('-') {
+            Some(s) => (-1.0, s),
+            None => (1.0, s.as_str()),
+        };
+        let digits: String = s
+            .strip_suffix('n')
+            .unwrap_or(s)
+            .chars()
+            .filter(|c| *c != '_')
+            .collect();
+        let radix = match digits.get(..2) {
+            Some("0x" | "0X") => 16,
+            Some("0o" | "0O") => 8,
+            Some("0b" | "0B") => 2,
+            _ => 10,
+        };
+        let number = if radix == 10 {
+            digits.parse::<f64>().ok()?
+        } else {
+            u128::from_str_radix(&digits[2..], radix).ok()? as f64
+        };
+        Some(sign * number)
+    }
+}
+
+// enum NAME { MEMBER = VALUE }, values are not needed for samples
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct EnumType {
+    pub name: String,
+    members: Vec<String>,
+    // set when the enum is imported into the component file
+    import: Option<Import>,
+}
+
+impl EnumType {
+    pub fn new(name: impl Into<String>, members: Vec<String>) -> Self {
+        Self {
+            name: name.into(),
+            members,
+            import: None,
+        }
+    }
+    // import { NAME as LOCAL } from "MODULE" された enum として扱う
+    pub fn imported(mut self, local: impl Into<String>, import: Import) -> Self {
+        self.name = local.into();
+        self.import = Some(import);
+        self
+    }
+    // module to import the enum from, None is the component file
+    fn import_module(&self) -> Option<&str> {
+        self.import.as_ref().map(|import| import.module.as_str())
+    }
+    // NAME or NAME as LOCAL
+    fn import_specifier(&self) -> String {
+        match &self.import {
+            Some(import) if import.name != self.name => {
+                format!("{} as {}", import.name, self.name)
+            }
+            _ => self.name.clone(),
+        }
+    }
+    // NAME.MEMBER, or NAME["MEMBER"] when MEMBER is not an identifier
+    fn values(&self) -> Vec<String> {
+        self.members
+            .iter()
+            .map(|member| {
+                let key = property_key(member);
+                if key == *member {
+                    format!("{}.{}", self.name, member)
+                } else {
+                    format!("{}[{}]", self.name, key)
+                }
+            })
+            .collect()
+    }
+}
+
+// TYPE, LABEL?: TYPE or ...TYPE of a tuple type
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct TupleElement {
+    pub label: Option<String>,
+    pub ty: Type,
+    optional: bool,
+    rest: bool,
+}
+
+impl TupleElement {
+    pub fn new(ty: Type) -> Self {
+        Self {
+            label: None,
+            ty,
+            optional: false,
+            rest: false,
+        }
+    }
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+    pub fn rest(mut self) -> Self {
+        self.rest = true;
+        self
+    }
+    // rest elements may be empty
+    fn is_sampled(&self, options: &SampleOptions) -> bool {
+        !(self.rest || options.omit_optional && self.optional)
+    }
+    pub fn to_str(&self) -> String {
+        let mut element = String::new();
+        if self.rest {
+            element.push_str("...");
+        }
+        match &self.label {
+            // [x?: number]
+            Some(label) => {
+                element.push_str(label);
+                if self.optional {
+                    element.push('?');
+                }
+                element.push_str(": ");
+                element.push_str(&self.ty.to_str());
+            }
+            // [number?]
+            None => {
+                element.push_str(&self.ty.to_str());
+                if self.optional {
+                    element.push('?');
+                }
+            }
+        }
+        element
+    }
+}
+
+// Storybook の argTypes に書く control
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum ArgType {
+    Radio(Vec<String>),
+    Select(Vec<String>),
+    Boolean,
+    Number,
+    Object,
+    Action,
+}
+
+impl ArgType {
+    // a few options fit in radio buttons
+    fn options(options: Vec<String>) -> Self {
+        if options.len() <= 3 {
+            Self::Radio(options)
+        } else {
+            Self::Select(options)
+        }
+    }
+    pub fn to_str(&self, key: &str) -> String {
+        match self {
+            Self::Radio(options) => {
+                format!(
+                    r#"{{ control: "radio", options: [{}] }}"#,
+                    options.join(", ")
+                )
+            }
+            Self::Select(options) => {
+                format!(
+                    r#"{{ control: "select", options: [{}] }}"#,
+                    options.join(", ")
+                )
+            }
+            Self::Boolean => r#"{ control: "boolean" }"#.to_string(),
+            Self::Number => r#"{ control: "number" }"#.to_string(),
+            Self::Object => r#"{ control: "object" }"#.to_string(),
+            Self::Action => format!(r#"{{ action: "{}" }}"#, key),
         }
     }
 }
@@ -177,6 +1178,15 @@ pub(super) enum PrimitiveType {
     Number,
     String,
     Boolean,
+    Null,
+    Undefined,
+    Any,
+    Unknown,
+    Never,
+    Object,
+    BigInt,
+    Symbol,
+    Void,
 }
 
 impl PrimitiveType {
@@ -185,6 +1195,15 @@ impl PrimitiveType {
             PrimitiveType::Number => "number".to_string(),
             PrimitiveType::String => "string".to_string(),
             PrimitiveType::Boolean => "boolean".to_string(),
+            PrimitiveType::Null => "null".to_string(),
+            PrimitiveType::Undefined => "undefined".to_string(),
+            PrimitiveType::Any => "any".to_string(),
+            PrimitiveType::Unknown => "unknown".to_string(),
+            PrimitiveType::Never => "never".to_string(),
+            PrimitiveType::Object => "object".to_string(),
+            PrimitiveType::BigInt => "bigint".to_string(),
+            PrimitiveType::Symbol => "symbol".to_string(),
+            PrimitiveType::Void => "void".to_string(),
         }
     }
     fn sample(&self) -> String {
@@ -192,12 +1211,24 @@ impl PrimitiveType {
             PrimitiveType::Number => "0".to_string(),
             PrimitiveType::String => "\"\"".to_string(),
             PrimitiveType::Boolean => "false".to_string(),
+            PrimitiveType::Null => "null".to_string(),
+            PrimitiveType::Object => "{}".to_string(),
+            PrimitiveType::BigInt => "0n".to_string(),
+            PrimitiveType::Symbol => "Symbol()".to_string(),
+            // nothing better than leaving the arg unset
+            PrimitiveType::Undefined
+            | PrimitiveType::Any
+            | PrimitiveType::Unknown
+            | PrimitiveType::Never
+            | PrimitiveType::Void => "undefined".to_string(),
         }
     }
+    fn is_nullish(&self) -> bool {
+        matches!(self, PrimitiveType::Null | PrimitiveType::Undefined)
+    }
 }
 
 #[cfg(test)]
-
 mod tests {
     use super::*;
 
@@ -207,16 +1238,154 @@ mod tests {
             inner: vec![
                 (
                     Key("timeOut".to_string()),
-                    Type::Primitive(PrimitiveType::Number),
+                    Property::new(Type::Primitive(PrimitiveType::Number)),
                 ),
                 (
                     Key("errorMessage".to_string()),
-                    Type::Primitive(PrimitiveType::String),
+                    Property::new(Type::Primitive(PrimitiveType::String)).optional(),
+                ),
+                (
+                    Key("id".to_string()),
+                    Property::new(Type::Primitive(PrimitiveType::String)).readonly(),
                 ),
             ]
             .into_iter()
             .collect(),
         };
-        assert!(props.to_str() == "{ errorMessage: string,timeOut: number, }");
+        assert!(props.to_str() == "{ errorMessage?: string,readonly id: string,timeOut: number, }");
+    }
+    #[test]
+    fn test_generic_sample() {
+        let generic = |name: &str, args: Vec<Type>| Type::Generic {
+            name: name.to_string(),
+            args,
+        };
+        let string = || Type::Primitive(PrimitiveType::String);
+        assert_eq!(
+            generic("Array", vec![string()]).sample("items", &SampleOptions::new(ActionStyle::Fn)),
+            "[\"\"]"
+        );
+        assert_eq!(
+            generic("Promise", vec![Type::Primitive(PrimitiveType::Void)])
+                .sample("", &SampleOptions::new(ActionStyle::Fn)),
+            "Promise.resolve()"
+        );
+        assert_eq!(
+            generic(
+                "Record",
+                vec![
+                    Type::Union(vec![
+                        Type::Literal(LiteralType::String("\"ja\"".to_string())),
+                        Type::Literal(LiteralType::String("\"en\"".to_string())),
+                    ]),
+                    generic("ReadonlyArray", vec![string()]),
+                ]
+            )
+            .sample("labels", &SampleOptions::new(ActionStyle::Fn)),
+            "{ \"ja\": [\"\"],\"en\": [\"\"], }"
+        );
+        assert_eq!(
+            generic("Map", vec![string(), string()])
+                .sample("map", &SampleOptions::new(ActionStyle::Fn)),
+            "undefined"
+        );
+        assert_eq!(
+            Type::Array(Box::new(Type::Alias("T".to_string())))
+                .sample("items", &SampleOptions::new(ActionStyle::Fn)),
+            "[undefined]"
+        );
+        assert_eq!(
+            generic(
+                "React.Dispatch",
+                vec![generic("React.SetStateAction", vec![string()])]
+            )
+            .sample("setValue", &SampleOptions::new(ActionStyle::Fn)),
+            "undefined"
+        );
+    }
+    #[test]
+    fn test_arg_type() {
+        let boolean = |b| Type::Literal(LiteralType::Boolean(b));
+        let number = |n: &str| Type::Literal(LiteralType::Number(n.to_string()));
+        assert_eq!(
+            Type::Primitive(PrimitiveType::Number).arg_type(),
+            Some(ArgType::Number)
+        );
+        assert_eq!(
+            Type::Union(vec![
+                boolean(true),
+                boolean(false),
+                Type::Primitive(PrimitiveType::Undefined),
+            ])
+            .arg_type(),
+            Some(ArgType::Boolean)
+        );
+        assert_eq!(
+            Type::Union(vec![number("1"), number("2")]).arg_type(),
+            Some(ArgType::Radio(vec!["1".to_string(), "2".to_string()]))
+        );
+        assert_eq!(ArgType::Number.to_str("gap"), r#"{ control: "number" }"#);
+    }
+    fn template(raw: &str, quasis: &[&str], placeholders: Vec<Type>) -> LiteralType {
+        LiteralType::Template {
+            raw: raw.to_string(),
+            quasis: quasis.iter().map(|quasi| quasi.to_string()).collect(),
+            placeholders,
+        }
+    }
+    #[test]
+    fn test_literal_type() {
+        let string = || Type::Primitive(PrimitiveType::String);
+        let union = Type::Union(vec![
+            Type::Literal(LiteralType::String("'info'".to_string())),
+            Type::Literal(LiteralType::String("\"warn\"".to_string())),
+            Type::Literal(LiteralType::Number("-1".to_string())),
+            Type::Literal(LiteralType::Boolean(true)),
+            Type::Literal(template("`btn-${string}`", &["btn-", ""], vec![string()])),
+        ]);
+        assert_eq!(
+            union.to_str(),
+            "'info' | \"warn\" | -1 | true | `btn-${string}`"
+        );
+        let options = SampleOptions::new(ActionStyle::Fn);
+        assert_eq!(union.sample("kind", &options), "'info'");
+        let number = Type::Primitive(PrimitiveType::Number);
+        let btn = template(
+            "`btn-${string}-${number}px`",
+            &["btn-", "-", "px"],
+            vec![string(), number],
+        );
+        assert_eq!(btn.sample(), "`btn--0px`");
+        assert_eq!(btn.value(), "btn-${string}-${number}px");
+        let btn = template(
+            "`btn-${\"sm\" | 'lg'}-${boolean}`",
+            &["btn-", "-", ""],
+            vec![
+                Type::Union(vec![
+                    Type::Literal(LiteralType::String("\"sm\"".to_string())),
+                    Type::Literal(LiteralType::String("'lg'".to_string())),
+                ]),
+                Type::Primitive(PrimitiveType::Boolean),
+            ],
+        );
+        assert_eq!(btn.sample(), "`btn-sm-false`");
+        assert_eq!(LiteralType::String("'info'".to_string()).value(), "info");
+    }
+    #[test]
+    fn test_number_literal() {
+        let number = |s: &str| LiteralType::Number(s.to_string());
+        assert_eq!(number("1_000").number(), Some(1000.0));
+        assert_eq!(number("0xff").number(), Some(255.0));
+        assert_eq!(number("0o17").number(), Some(15.0));
+        assert_eq!(number("0b1010").number(), Some(10.0));
+        assert_eq!(number("1.5e3").number(), Some(1500.0));
+        assert_eq!(number("-1e-3").number(), Some(-0.001));
+        assert_eq!(number(".5").number(), Some(0.5));
+        assert_eq!(number("10n").number(), Some(10.0));
+        assert_eq!(LiteralType::Boolean(true).number(), None);
+        // sample は書かれた通り, value は数値として
+        assert_eq!(number("0xff").sample(), "0xff");
+        assert_eq!(number("0xff").value(), "255");
+        assert_eq!(number("1_000").value(), "1000");
     }
 }
@@ -0,0 +1,290 @@
+use crate::component::{Key, LiteralType, ObjectType, Property, Type};
+
+// 型の参照や Partial<Base>, Omit<Base, "onClick"> & { ... } のような
+// 組み込みの utility type を展開し, sample に使う実際の型を求めるもの
+pub(super) struct Evaluator<F> {
+    // NAME -> 宣言されている型, 見つからなければ None
+    lookup: F,
+    // 展開中の型, type Tree = { children: Tree[] } のような再帰で止まるように
+    expanding: Vec<String>,
+}
+
+impl<E, F> Evaluator<F>
+where
+    F: FnMut(&str) -> Result<Option<Type>, E>,
+{
+    pub fn new(lookup: F) -> Self {
+        Self {
+            lookup,
+            expanding: Vec::new(),
+        }
+    }
+    // 分からない型はそのまま残す
+    pub fn evaluate(&mut self, ty: &Type) -> Result<Type, E> {
+        match ty {
+            Type::Alias(name) => Ok(self.evaluate_alias(name)?.unwrap_or_else(|| ty.clone())),
+            Type::Object(props) => Ok(Type::Object(self.evaluate_object(props)?)),
+            Type::Union(tys) => Ok(Type::Union(self.evaluate_all(tys)?)),
+            Type::Intersection(tys) => self.evaluate_intersection(tys),
+            Type::Array(ty) => Ok(Type::Array(Box::new(self.evaluate(ty)?))),
+            Type::Tuple(elements) => {
+                let mut elements = elements.clone();
+                for element in &mut elements {
+                    element.ty = self.evaluate(&element.ty)?;
+                }
+                Ok(Type::Tuple(elements))
+            }
+            Type::Generic { name, args } => self.evaluate_generic(name, args),
+            // `btn-${Size}` の Size
+            Type::Literal(LiteralType::Template {
+                raw,
+                quasis,
+                placeholders,
+            }) => Ok(Type::Literal(LiteralType::Template {
+                raw: raw.clone(),
+                quasis: quasis.clone(),
+                placeholders: self.evaluate_all(placeholders)?,
+            })),
+            Type::Keyof(ty) => self.evaluate_keyof(ty),
+            Type::Indexed { object, index } => self.evaluate_indexed(object, index),
+            // 関数の引数や戻り値は sample に使わない
+            _ => Ok(ty.clone()),
+        }
+    }
+    fn evaluate_all(&mut self, tys: &[Type]) -> Result<Vec<Type>, E> {
+        tys.iter().map(|ty| self.evaluate(ty)).collect()
+    }
+    fn evaluate_alias(&mut self, name: &str) -> Result<Option<Type>, E> {
+        if self.expanding.iter().any(|expanding| expanding == name) {
+            return Ok(None);
+        }
+        let Some(ty) = (self.lookup)(name)? else {
+            return Ok(None);
+        };
+        self.expanding.push(name.to_string());
+        let evaluated = self.evaluate(&ty);
+        self.expanding.pop();
+        evaluated.map(Some)
+    }
+    fn evaluate_object(&mut self, props: &ObjectType) -> Result<ObjectType, E> {
+        let mut evaluated = ObjectType::new();
+        for (key, property) in props.properties() {
+            let mut property = property.clone();
+            property.ty = self.evaluate(&property.ty)?;
+            evaluated.insert_property(key.clone(), property);
+        }
+        Ok(evaluated)
+    }
+    // A & B & Unknown の object である A と B を一つの object にまとめる
+    // 分からない Unknown は残す
+    fn evaluate_intersection(&mut self, tys: &[Type]) -> Result<Type, E> {
+        let mut merged = None;
+        let mut rest = Vec::new();
+        for ty in self.evaluate_all(tys)? {
+            match ty {
+                Type::Object(props) => {
+                    merged = Some(merged.unwrap_or_else(ObjectType::new).merge(props))
+                }
+                ty => rest.push(ty),
+            }
+        }
+        Ok(match merged {
+            Some(merged) if rest.is_empty() => Type::Object(merged),
+            Some(merged) => {
+                Type::Intersection(std::iter::once(Type::Object(merged)).chain(rest).collect())
+            }
+            None => Type::Intersection(rest),
+        })
+    }
+    // keyof { a, b } -> "a" | "b"
+    fn evaluate_keyof(&mut self, ty: &Type) -> Result<Type, E> {
+        let ty = self.evaluate(ty)?;
+        let keys = match &ty {
+            Type::Object(props) => props
+                .properties()
+                .map(|(key, _)| Type::Literal(LiteralType::String(format!("\"{}\"", key.0))))
+                .collect(),
+            _ => Vec::new(),
+        };
+        Ok(one_or_union(keys).unwrap_or_else(|| Type::Keyof(Box::new(ty))))
+    }
+    // { a: A, b: B }["a" | "b"] -> A | B
+    fn evaluate_indexed(&mut self, object: &Type, index: &Type) -> Result<Type, E> {
+        let object = self.evaluate(object)?;
+        let index = self.evaluate(index)?;
+        let members = match (&object, literal_keys(&index)) {
+            (Type::Object(props), Some(keys)) => keys
+                .iter()
+                .map(|key| props.get(key).map(|property| property.ty.clone()))
+                .collect::<Option<Vec<_>>>()
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+        Ok(one_or_union(members).unwrap_or_else(|| Type::Indexed {
+            object: Box::new(object),
+            index: Box::new(index),
+        }))
+    }
+    fn evaluate_generic(&mut self, name: &str, args: &[Type]) -> Result<Type, E> {
+        let args = self.evaluate_all(args)?;
+        let evaluated = match (name, args.as_slice()) {
+            ("Partial", [Type::Object(props)]) => Some(props.map_properties(Property::optional)),
+            ("Required", [Type::Object(props)]) => Some(props.map_properties(Property::required)),
+            ("Readonly", [Type::Object(props)]) => Some(props.map_properties(Property::readonly)),
+            ("Pick", [Type::Object(props), keys]) => {
+                literal_keys(keys).map(|keys| props.retain(|key| keys.contains(&key.0)))
+            }
+            ("Omit", [Type::Object(props), keys]) => {
+                literal_keys(keys).map(|keys| props.retain(|key| !keys.contains(&key.0)))
+            }
+            // Record<"a" | "b", V> -> { a: V, b: V }
+            ("Record", [keys, value]) => literal_keys(keys).map(|keys| {
+                let mut props = ObjectType::new();
+                for key in keys {
+                    props.insert_property(Key(key), Property::new(value.clone()));
+                }
+                props
+            }),
+            _ => None,
+        };
+        Ok(match evaluated {
+            Some(props) => Type::Object(props),
+            None => Type::Generic {
+                name: name.to_string(),
+                args,
+            },
+        })
+    }
+}
+
+// [A] -> A, [A, B] -> A | B, [] -> None
+fn one_or_union(mut tys: Vec<Type>) -> Option<Type> {
+    match tys.len() {
+        0 => None,
+        1 => tys.pop(),
+        _ => Some(Type::Union(tys)),
+    }
+}
+
+// "a" | "b" -> [a, b], string のように列挙できなければ None
+fn literal_keys(keys: &Type) -> Option<Vec<String>> {
+    match keys {
+        Type::Literal(literal) => Some(vec![literal.value()]),
+        Type::Union(keys) => keys
+            .iter()
+            .map(literal_keys)
+            .collect::<Option<Vec<_>>>()
+            .map(|keys| keys.concat()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::PrimitiveType;
+
+    fn string() -> Type {
+        Type::Primitive(PrimitiveType::String)
+    }
+    fn literal(s: &str) -> Type {
+        Type::Literal(LiteralType::String(format!("\"{}\"", s)))
+    }
+    fn generic(name: &str, args: Vec<Type>) -> Type {
+        Type::Generic {
+            name: name.to_string(),
+            args,
+        }
+    }
+    #[test]
+    fn test_evaluate_utility_types() {
+        let mut base = ObjectType::new();
+        base.insert(Key("label".to_string()), string());
+        base.insert_property(Key("title".to_string()), Property::new(string()).optional());
+        base.insert(
+            Key("onClick".to_string()),
+            Type::Function {
+                params: Vec::new(),
+                ret: Box::new(Type::Primitive(PrimitiveType::Void)),
+            },
+        );
+        let mut evaluator = Evaluator::new(|name: &str| {
+            Ok::<_, ()>((name == "BaseProps").then(|| Type::Object(base.clone())))
+        });
+
+        let mut props = ObjectType::new();
+        props.insert_property(Key("label".to_string()), Property::new(string()).optional());
+        props.insert_property(Key("title".to_string()), Property::new(string()).optional());
+        assert_eq!(
+            evaluator.evaluate(&generic(
+                "Partial",
+                vec![generic(
+                    "Omit",
+                    vec![Type::Alias("BaseProps".to_string()), literal("onClick")]
+                )]
+            )),
+            Ok(Type::Object(props))
+        );
+
+        let mut props = ObjectType::new();
+        props.insert(Key("title".to_string()), string());
+        props.insert(Key("size".to_string()), literal("sm"));
+        assert_eq!(
+            evaluator.evaluate(&Type::Intersection(vec![
+                generic(
+                    "Required",
+                    vec![generic(
+                        "Pick",
+                        vec![Type::Alias("BaseProps".to_string()), literal("title")]
+                    )]
+                ),
+                generic("Record", vec![literal("size"), literal("sm")]),
+            ])),
+            Ok(Type::Object(props))
+        );
+
+        // 分からない InputFieldProps は残して, 分かる object だけをまとめる
+        let mut props = ObjectType::new();
+        props.insert(Key("label".to_string()), string());
+        props.insert(Key("size".to_string()), literal("sm"));
+        let mut size = ObjectType::new();
+        size.insert(Key("size".to_string()), literal("sm"));
+        assert_eq!(
+            evaluator.evaluate(&Type::Intersection(vec![
+                generic(
+                    "Pick",
+                    vec![Type::Alias("BaseProps".to_string()), literal("label")]
+                ),
+                Type::Alias("InputFieldProps".to_string()),
+                Type::Object(size),
+            ])),
+            Ok(Type::Intersection(vec![
+                Type::Object(props),
+                Type::Alias("InputFieldProps".to_string()),
+            ]))
+        );
+
+        // 分からない型はそのまま
+        let unknown = generic(
+            "Omit",
+            vec![Type::Alias("Unknown".to_string()), literal("onClick")],
+        );
+        assert_eq!(evaluator.evaluate(&unknown), Ok(unknown));
+    }
+    #[test]
+    fn test_evaluate_recursive_alias() {
+        let mut tree = ObjectType::new();
+        tree.insert(
+            Key("children".to_string()),
+            Type::Array(Box::new(Type::Alias("Tree".to_string()))),
+        );
+        let mut evaluator = Evaluator::new(|name: &str| {
+            Ok::<_, ()>((name == "Tree").then(|| Type::Object(tree.clone())))
+        });
+        // 展開中の Tree はそのまま残す
+        assert_eq!(
+            evaluator.evaluate(&Type::Alias("Tree".to_string())),
+            Ok(Type::Object(tree.clone()))
+        );
+    }
+}
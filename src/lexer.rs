@@ -1,16 +1,76 @@
 use std::str::Chars;
 
-use crate::token::{TSXToken, TSXTokenType};
+use crate::token::{Position, Span, TSXToken, TSXTokenType};
 
 pub(super) struct Lexer<'a> {
     input: Chars<'a>,
+    input_len: usize,
     focus: char,
+    // position of focus
+    offset: usize,
+    line: usize,
+    column: usize,
+    // whether an expression can start at focus, such as after ( = return
+    // / there is a regex and < can be a JSX element
+    expression_start: bool,
+    // whether the last < starts a JSX element such as <div> or <>
+    jsx_start: bool,
 }
 impl Lexer<'_> {
-    pub fn new(input: &str) -> Lexer {
+    pub fn new(input: &str) -> Lexer<'_> {
+        let input_len = input.len();
         let input = input.chars();
         let focus = ' ';
-        Lexer { input, focus }
+        Lexer {
+            input,
+            input_len,
+            focus,
+            offset: 0,
+            line: 1,
+            column: 0,
+            expression_start: true,
+            jsx_start: false,
+        }
+    }
+    // lexes source from start, spans are still offsets in the whole source
+    pub fn new_at(source: &str, start: Position) -> Lexer<'_> {
+        Lexer {
+            input: source[start.offset..].chars(),
+            input_len: source.len(),
+            focus: ' ',
+            offset: start.offset,
+            line: start.line,
+            column: start.column - 1,
+            expression_start: true,
+            jsx_start: false,
+        }
+    }
+    // only use on the ` of a template literal
+    // `a-${A}-${B}` -> ["a-", "-", ""] and the positions of the { of ${A} and ${B}
+    pub fn split_template(source: &str, start: Position) -> (Vec<String>, Vec<Position>) {
+        let mut lexer = Lexer::new_at(source, start);
+        lexer.set_next_char();
+        let mut quasis = Vec::new();
+        let mut placeholders = Vec::new();
+        let mut quasi = String::new();
+        while lexer.set_next_char() {
+            match lexer.focus {
+                '`' => break,
+                '\\' => {
+                    quasi.push('\\');
+                    lexer.scan_escape(&mut quasi);
+                }
+                '$' if lexer.input.clone().next() == Some('{') => {
+                    lexer.set_next_char();
+                    placeholders.push(lexer.position());
+                    lexer.scan_template_expression(&mut String::new());
+                    quasis.push(std::mem::take(&mut quasi));
+                }
+                c => quasi.push(c),
+            }
+        }
+        quasis.push(quasi);
+        (quasis, placeholders)
     }
     fn char_to_token(ch: char) -> TSXToken {
         match ch {
@@ -42,8 +102,209 @@ impl Lexer<'_> {
     }
     pub fn next_token(&mut self) -> TSXToken {
         self.skip_whitespace();
+        let start = self.position();
+        let mut token = self.read_token();
+        token.span = Span::new(start, self.position());
+        if !matches!(
+            token.token_type,
+            TSXTokenType::Comment | TSXTokenType::StartDocComment | TSXTokenType::EndDocComment
+        ) {
+            self.expression_start = Self::starts_expression(&token);
+        }
+        token
+    }
+    // whether the last < starts a JSX element, not a type parameter or less than
+    pub fn is_jsx_start(&self) -> bool {
+        self.jsx_start
+    }
+    // only use when the last token is < of a JSX element
+    // skips <Tag attr="" {...spread} attr={expr}>text {expr} <Child /></Tag> and <>...</>
+    pub fn skip_jsx_element(&mut self) {
+        // elements not closed yet
+        let mut depth = 0;
+        let mut closing = false;
+        loop {
+            let self_closing = self.skip_jsx_tag();
+            if closing {
+                depth -= 1;
+            } else if !self_closing {
+                depth += 1;
+            }
+            if depth == 0 {
+                break;
+            }
+            match self.skip_jsx_children() {
+                Some(next_closing) => closing = next_closing,
+                None => break,
+            }
+        }
+        self.expression_start = false;
+    }
+    // only use when focus is after < or </
+    // returns whether the tag is self closing
+    fn skip_jsx_tag(&mut self) -> bool {
+        loop {
+            match self.focus {
+                '>' => {
+                    self.set_next_char();
+                    return false;
+                }
+                '/' if self.input.clone().next() == Some('>') => {
+                    self.set_next_char();
+                    self.set_next_char();
+                    return true;
+                }
+                // attribute values are not escaped in JSX
+                '"' | '\'' => {
+                    let quote = self.focus;
+                    while self.set_next_char() && self.focus != quote {}
+                    self.set_next_char();
+                }
+                '{' => self.skip_jsx_expression(),
+                _ => {
+                    if !self.set_next_char() {
+                        return true;
+                    }
+                }
+            }
+        }
+    }
+    // only use when focus is after > of an open tag
+    // skips text and {expr} up to the next tag and returns whether it is a closing tag
+    // None at the end of input
+    fn skip_jsx_children(&mut self) -> Option<bool> {
+        loop {
+            match self.focus {
+                '<' => {
+                    self.set_next_char();
+                    if self.focus == '/' {
+                        self.set_next_char();
+                        return Some(true);
+                    }
+                    return Some(false);
+                }
+                '{' => self.skip_jsx_expression(),
+                _ => {
+                    if !self.set_next_char() {
+                        return None;
+                    }
+                }
+            }
+        }
+    }
+    // only use when focus is { in JSX
+    // the expression is read as tokens, so "}" and <Child /> in it are skipped as well
+    fn skip_jsx_expression(&mut self) {
+        let mut depth = 0;
+        loop {
+            let token = self.next_token();
+            match token.token_type {
+                TSXTokenType::LCurlyBracket => depth += 1,
+                TSXTokenType::RCurlyBracket => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return;
+                    }
+                }
+                TSXTokenType::LTag if self.jsx_start => self.skip_jsx_element(),
+                TSXTokenType::Eof => return,
+                _ => {}
+            }
+        }
+    }
+    // <div>, <> or <Foo.Bar after ( = return etc.
+    // <T,>() => {} and <T extends U>() => {} are type parameters
+    fn at_jsx_element(&self) -> bool {
+        if !self.expression_start {
+            return false;
+        }
+        if self.focus == '>' {
+            return true;
+        }
+        if !Self::is_letter(self.focus) {
+            return false;
+        }
+        let rest = self.input.as_str().trim_start_matches(|c: char| {
+            Self::is_letter(c) || Self::is_number(c) || matches!(c, '.' | '-' | ':')
+        });
+        let rest = rest.trim_start();
+        !(rest.starts_with(',')
+            || rest
+                .strip_prefix("extends")
+                .is_some_and(|rest| rest.starts_with(char::is_whitespace)))
+    }
+    // whether an expression can start after the token
+    // a / after ) ] } a name or a literal is a division
+    fn starts_expression(token: &TSXToken) -> bool {
+        if token.token_type == TSXTokenType::Ident {
+            return matches!(
+                token.literal.as_str(),
+                "typeof"
+                    | "instanceof"
+                    | "in"
+                    | "of"
+                    | "new"
+                    | "delete"
+                    | "throw"
+                    | "case"
+                    | "do"
+                    | "yield"
+                    | "await"
+            );
+        }
+        matches!(
+            token.token_type,
+            TSXTokenType::LParentheses
+                | TSXTokenType::LBracket
+                | TSXTokenType::LCurlyBracket
+                | TSXTokenType::Comma
+                | TSXTokenType::Semicolon
+                | TSXTokenType::Colon
+                | TSXTokenType::Question
+                | TSXTokenType::NullishCoalescing
+                | TSXTokenType::Assign
+                | TSXTokenType::Arrow
+                | TSXTokenType::Eq
+                | TSXTokenType::NotEq
+                | TSXTokenType::And
+                | TSXTokenType::Or
+                | TSXTokenType::Pipe
+                | TSXTokenType::Bang
+                | TSXTokenType::Plus
+                | TSXTokenType::Minus
+                | TSXTokenType::Add
+                | TSXTokenType::Sub
+                | TSXTokenType::Asterisk
+                | TSXTokenType::Slash
+                | TSXTokenType::LTag
+                | TSXTokenType::RTag
+                | TSXTokenType::LtEq
+                | TSXTokenType::GtEq
+                | TSXTokenType::CrossEqual
+                | TSXTokenType::IncrementEqual
+                | TSXTokenType::DecrementEqual
+                | TSXTokenType::SlashEqual
+                | TSXTokenType::Return
+                | TSXTokenType::Void
+                | TSXTokenType::Default
+                | TSXTokenType::Else
+        )
+    }
+    fn position(&self) -> Position {
+        Position::new(self.offset, self.line, self.column)
+    }
+    fn read_token(&mut self) -> TSXToken {
         match self.focus {
             // effect only one char
+            // .5
+            '.' if self
+                .input
+                .clone()
+                .next()
+                .is_some_and(|c| c.is_ascii_digit()) =>
+            {
+                TSXToken::new(TSXTokenType::NumberLiteral, self.read_number())
+            }
             ',' | ';' | '(' | ')' | '{' | '}' | ':' | '#' | '.' | '&' | '[' | ']' => {
                 let token = Self::char_to_token(self.focus);
                 self.set_next_char();
@@ -100,7 +361,10 @@ impl Lexer<'_> {
                         self.set_next_char();
                         TSXToken::new(TSXTokenType::CloseLTag, "</")
                     }
-                    _ => TSXToken::new(TSXTokenType::LTag, "<"),
+                    _ => {
+                        self.jsx_start = self.at_jsx_element();
+                        TSXToken::new(TSXTokenType::LTag, "<")
+                    }
                 }
             }
             '>' => {
@@ -117,9 +381,10 @@ impl Lexer<'_> {
                 self.set_next_char();
                 match self.focus {
                     '/' => TSXToken::new(TSXTokenType::Comment, self.read_comment()),
-                    '*' => {
-                        self.set_next_char();
-                        TSXToken::new(TSXTokenType::StartDocComment, "/*")
+                    '*' => TSXToken::new(TSXTokenType::Comment, self.read_block_comment()),
+                    // const re = /=+/ but a /= b
+                    _ if self.expression_start => {
+                        TSXToken::new(TSXTokenType::RegexLiteral, self.read_regex())
                     }
                     '=' => {
                         self.set_next_char();
@@ -135,11 +400,6 @@ impl Lexer<'_> {
             '*' => {
                 self.set_next_char();
                 match self.focus {
-                    '/' => {
-                        self.set_next_char();
-                        let comment = self.read_doc_comment();
-                        TSXToken::new(TSXTokenType::EndDocComment, comment)
-                    }
                     '=' => {
                         self.set_next_char();
                         TSXToken::new(TSXTokenType::CrossEqual, "*=")
@@ -175,14 +435,17 @@ impl Lexer<'_> {
                     _ => TSXToken::new(TSXTokenType::Sub, "-"),
                 }
             }
-            '"' => {
+            '"' | '\'' => {
                 let literal = self.read_string();
                 TSXToken::new(TSXTokenType::StringLiteral, literal)
             }
-            '\'' => {
+            '`' => {
+                let mut raw = String::new();
+                self.scan_template(&mut raw);
                 self.set_next_char();
-                let literal = self.read_string();
-                TSXToken::new(TSXTokenType::StringLiteral, literal)
+                let template = raw.strip_prefix('`').unwrap_or(&raw);
+                let template = template.strip_suffix('`').unwrap_or(template);
+                TSXToken::new(TSXTokenType::TemplateLiteral, template)
             }
             c => {
                 if Self::is_letter(c) {
@@ -196,9 +459,14 @@ impl Lexer<'_> {
                 if Self::is_number(c) {
                     let literal = self.read_number();
                     TSXToken::new(TSXTokenType::NumberLiteral, literal)
-                } else {
+                } else if c.is_whitespace() {
+                    // focus is left as ' ' at the end of input
                     self.set_next_char();
                     TSXToken::new(TSXTokenType::Eof, "")
+                } else {
+                    // % ^ ~ @ and so on
+                    self.set_next_char();
+                    Self::char_to_token(c)
                 }
             }
         }
@@ -207,14 +475,18 @@ impl Lexer<'_> {
         while self.focus.is_whitespace() && self.set_next_char() {}
     }
 
-    // only use when focus is doc comment token
-    fn read_doc_comment(&mut self) -> String {
-        let mut comment = String::new();
-        while self.focus != '*' && self.set_next_char() {
+    // only use when focus is * of /*
+    // returns the whole comment as written, /** doc */, so ( and { in it are not tokens
+    fn read_block_comment(&mut self) -> String {
+        let mut comment = String::from("/*");
+        let mut last = ' ';
+        while self.set_next_char() {
             comment.push(self.focus);
-        }
-        if self.set_next_char() && self.focus == '/' {
-            self.set_next_char();
+            if last == '*' && self.focus == '/' {
+                self.set_next_char();
+                break;
+            }
+            last = self.focus;
         }
         comment
     }
@@ -226,14 +498,130 @@ impl Lexer<'_> {
         }
         comment
     }
+    // only use when focus is after the first /
+    // returns the regex as written, /^\d+\/x/g
+    fn read_regex(&mut self) -> String {
+        let mut regex = String::from("/");
+        // [/] does not end the regex
+        let mut in_class = false;
+        // a regex can not span lines, stop there when it is not closed
+        while self.focus != '\n' {
+            regex.push(self.focus);
+            match self.focus {
+                '\\' => {
+                    if !self.set_next_char() || self.focus == '\n' {
+                        break;
+                    }
+                    regex.push(self.focus);
+                }
+                '[' => in_class = true,
+                ']' => in_class = false,
+                '/' if !in_class => {
+                    self.set_next_char();
+                    // flags
+                    while Self::is_letter(self.focus) {
+                        regex.push(self.focus);
+                        self.set_next_char();
+                    }
+                    return regex;
+                }
+                _ => {}
+            }
+            if !self.set_next_char() {
+                break;
+            }
+        }
+        regex
+    }
+    // only use when focus is ' or "
+    // returns the value, 'it\'s' -> it's
     fn read_string(&mut self) -> String {
+        let quote = self.focus;
         let mut string = String::new();
-        while self.set_next_char() && self.focus != '"' {
-            string.push(self.focus);
+        // a string can not span lines, stop there when it is not closed
+        while self.set_next_char() && self.focus != quote && self.focus != '\n' {
+            if self.focus != '\\' {
+                string.push(self.focus);
+                continue;
+            }
+            if !self.set_next_char() {
+                break;
+            }
+            match self.focus {
+                'n' => string.push('\n'),
+                'r' => string.push('\r'),
+                't' => string.push('\t'),
+                // line continuation
+                '\n' => {}
+                c => string.push(c),
+            }
         }
         self.set_next_char();
         string
     }
+    // only use when focus is ' or ", pushes the string as written
+    // focus is left on the closing quote
+    fn scan_string(&mut self, raw: &mut String) {
+        let quote = self.focus;
+        raw.push(quote);
+        while self.set_next_char() {
+            raw.push(self.focus);
+            match self.focus {
+                '\\' => self.scan_escape(raw),
+                '\n' => return,
+                c if c == quote => return,
+                _ => {}
+            }
+        }
+    }
+    // only use when focus is \, pushes the escaped char as written
+    fn scan_escape(&mut self, raw: &mut String) {
+        if self.set_next_char() {
+            raw.push(self.focus);
+        }
+    }
+    // only use when focus is `, pushes the template as written
+    // focus is left on the closing `
+    fn scan_template(&mut self, raw: &mut String) {
+        raw.push('`');
+        while self.set_next_char() {
+            raw.push(self.focus);
+            match self.focus {
+                '`' => return,
+                '\\' => self.scan_escape(raw),
+                '$' if self.input.clone().next() == Some('{') => {
+                    self.set_next_char();
+                    raw.push(self.focus);
+                    self.scan_template_expression(raw);
+                }
+                _ => {}
+            }
+        }
+    }
+    // only use when focus is { of ${
+    // strings and templates in the expression may have their own { } and `
+    // focus is left on the closing }
+    fn scan_template_expression(&mut self, raw: &mut String) {
+        let mut depth = 1;
+        while self.set_next_char() {
+            match self.focus {
+                '`' => self.scan_template(raw),
+                '"' | '\'' => self.scan_string(raw),
+                '{' => {
+                    depth += 1;
+                    raw.push('{');
+                }
+                '}' => {
+                    raw.push('}');
+                    depth -= 1;
+                    if depth == 0 {
+                        return;
+                    }
+                }
+                c => raw.push(c),
+            }
+        }
+    }
     fn read_word(&mut self) -> String {
         let mut word = String::new();
         // first char is letter(not number or symbol)
@@ -248,27 +636,80 @@ impl Lexer<'_> {
         }
         word
     }
+    // only use when focus is a number or . of .5
+    // returns the number as written, 1_000, 0xff, 0o17, 0b1010, 1.5e-3 or 10n
     fn read_number(&mut self) -> String {
         let mut number = String::new();
-        // next char is number
-        while Self::is_number(self.focus) {
+        let radix_prefix = matches!(
+            self.input.clone().next(),
+            Some('x' | 'X' | 'o' | 'O' | 'b' | 'B')
+        );
+        if self.focus == '0' && radix_prefix {
+            number.push(self.focus);
+            self.set_next_char();
+            number.push(self.focus);
+            self.set_next_char();
+            // binary and octal digits are hex digits as well
+            while self.focus.is_ascii_hexdigit() || self.focus == '_' {
+                number.push(self.focus);
+                self.set_next_char();
+            }
+        } else {
+            self.read_digits(&mut number);
+            if self.focus == '.' {
+                number.push(self.focus);
+                self.set_next_char();
+                self.read_digits(&mut number);
+            }
+            // 1e3, 1e+3, 1e-3 but not 1em
+            let mut rest = self.input.clone();
+            let exponent = match rest.next() {
+                Some('+' | '-') => rest.next().is_some_and(|c| c.is_ascii_digit()),
+                Some(c) => c.is_ascii_digit(),
+                None => false,
+            };
+            if matches!(self.focus, 'e' | 'E') && exponent {
+                number.push(self.focus);
+                self.set_next_char();
+                number.push(self.focus);
+                self.set_next_char();
+                self.read_digits(&mut number);
+            }
+        }
+        // bigint
+        if self.focus == 'n' {
             number.push(self.focus);
             self.set_next_char();
         }
         number
     }
+    // 1_000 has separators
+    fn read_digits(&mut self, number: &mut String) {
+        while Self::is_number(self.focus) || self.focus == '_' {
+            number.push(self.focus);
+            self.set_next_char();
+        }
+    }
     fn is_number(ch: char) -> bool {
         ch.is_numeric()
     }
     fn is_letter(ch: char) -> bool {
-        ch.is_alphabetic() || ch == '_'
+        ch.is_alphabetic() || ch == '_' || ch == '$'
     }
     fn set_next_char(&mut self) -> bool {
+        if self.focus == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
         if let Some(c) = self.input.next() {
             self.focus = c;
+            self.offset = self.input_len - self.input.as_str().len() - c.len_utf8();
             true
         } else {
             self.focus = ' ';
+            self.offset = self.input_len;
             false
         }
     }
@@ -291,6 +732,12 @@ fn tsx_keywords(s: &str) -> Option<TSXToken> {
         "class" => Some(TSXToken::new(TSXTokenType::Class, s)),
         "var" => Some(TSXToken::new(TSXTokenType::Var, s)),
         "from" => Some(TSXToken::new(TSXTokenType::From, s)),
+        "interface" => Some(TSXToken::new(TSXTokenType::Interface, s)),
+        "extends" => Some(TSXToken::new(TSXTokenType::Extends, s)),
+        "enum" => Some(TSXToken::new(TSXTokenType::Enum, s)),
+        "null" => Some(TSXToken::new(TSXTokenType::Null, s)),
+        "undefined" => Some(TSXToken::new(TSXTokenType::Undefined, s)),
+        "void" => Some(TSXToken::new(TSXTokenType::Void, s)),
         _ => None,
     }
 }
@@ -457,4 +904,199 @@ export const ErrorAlert: FC<Props> = (props: Props) => {}
         );
         assert_eq!(lexer.next_token(), TSXToken::new(TSXTokenType::Eof, ""));
     }
+    #[test]
+    fn test_lexer_span() {
+        let content = r#"
+type Props = {
+  timeOut: number;
+};"#;
+        let mut lexer = Lexer::new(content);
+        let mut spans = Vec::new();
+        loop {
+            let token = lexer.next_token();
+            if token.token_type == TSXTokenType::Eof {
+                break;
+            }
+            spans.push(token.span);
+        }
+        assert_eq!(
+            spans,
+            vec![
+                Span::new(Position::new(1, 2, 1), Position::new(5, 2, 5)),
+                Span::new(Position::new(6, 2, 6), Position::new(11, 2, 11)),
+                Span::new(Position::new(12, 2, 12), Position::new(13, 2, 13)),
+                Span::new(Position::new(14, 2, 14), Position::new(15, 2, 15)),
+                Span::new(Position::new(18, 3, 3), Position::new(25, 3, 10)),
+                Span::new(Position::new(25, 3, 10), Position::new(26, 3, 11)),
+                Span::new(Position::new(27, 3, 12), Position::new(33, 3, 18)),
+                Span::new(Position::new(33, 3, 18), Position::new(34, 3, 19)),
+                Span::new(Position::new(35, 4, 1), Position::new(36, 4, 2)),
+                Span::new(Position::new(36, 4, 2), Position::new(37, 4, 3)),
+            ]
+        );
+    }
+    #[test]
+    fn test_lexer_span_multibyte() {
+        let mut lexer = Lexer::new("// コメント\nexport");
+        lexer.next_token();
+        let export = lexer.next_token();
+        assert_eq!(export.token_type, TSXTokenType::Export);
+        assert_eq!(
+            export.span,
+            Span::new(Position::new(16, 2, 1), Position::new(22, 2, 7))
+        );
+    }
+    #[test]
+    fn test_lexer_strings() {
+        let content = r#"'it\'s' "a\"b" 'x"y' `${a}px` `${x ? `${y}` : "}"}-${z}`;"#;
+        let mut lexer = Lexer::new(content);
+        assert_eq!(
+            lexer.next_token(),
+            TSXToken::new(TSXTokenType::StringLiteral, "it's")
+        );
+        assert_eq!(
+            lexer.next_token(),
+            TSXToken::new(TSXTokenType::StringLiteral, "a\"b")
+        );
+        assert_eq!(
+            lexer.next_token(),
+            TSXToken::new(TSXTokenType::StringLiteral, "x\"y")
+        );
+        assert_eq!(
+            lexer.next_token(),
+            TSXToken::new(TSXTokenType::TemplateLiteral, "${a}px")
+        );
+        assert_eq!(
+            lexer.next_token(),
+            TSXToken::new(TSXTokenType::TemplateLiteral, r#"${x ? `${y}` : "}"}-${z}"#)
+        );
+        assert_eq!(
+            lexer.next_token(),
+            TSXToken::new(TSXTokenType::Semicolon, ";")
+        );
+        assert_eq!(lexer.next_token(), TSXToken::new(TSXTokenType::Eof, ""));
+    }
+    #[test]
+    fn test_lexer_skip_jsx() {
+        let content = r#"(<>
+  <input type="text" value='{' onChange={(e) => set({ v: e.target.value })} />
+  Don't {items.map((item) => <li key={item}>{"}"}</li>)}
+</>) < x"#;
+        let mut lexer = Lexer::new(content);
+        assert_eq!(
+            lexer.next_token(),
+            TSXToken::new(TSXTokenType::LParentheses, "(")
+        );
+        assert_eq!(lexer.next_token(), TSXToken::new(TSXTokenType::LTag, "<"));
+        assert!(lexer.is_jsx_start());
+        lexer.skip_jsx_element();
+        assert_eq!(
+            lexer.next_token(),
+            TSXToken::new(TSXTokenType::RParentheses, ")")
+        );
+        // a < b や <T,>() => {} は JSX ではない
+        assert_eq!(lexer.next_token(), TSXToken::new(TSXTokenType::LTag, "<"));
+        assert!(!lexer.is_jsx_start());
+        assert_eq!(lexer.next_token(), TSXToken::new(TSXTokenType::Ident, "x"));
+        let mut lexer = Lexer::new("= <T,>(x: T) => x");
+        lexer.next_token();
+        lexer.next_token();
+        assert!(!lexer.is_jsx_start());
+    }
+    #[test]
+    fn test_lexer_regex() {
+        let content = r#"const re = /^\d+\/x/g; a / 2 /b; if (/[/'`]/.test(s)) return /=/;"#;
+        let mut lexer = Lexer::new(content);
+        let mut tokens = Vec::new();
+        loop {
+            let token = lexer.next_token();
+            if token.token_type == TSXTokenType::Eof {
+                break;
+            }
+            tokens.push(token);
+        }
+        assert_eq!(
+            tokens,
+            vec![
+                TSXToken::new(TSXTokenType::Const, "const"),
+                TSXToken::new(TSXTokenType::Ident, "re"),
+                TSXToken::new(TSXTokenType::Assign, "="),
+                TSXToken::new(TSXTokenType::RegexLiteral, r"/^\d+\/x/g"),
+                TSXToken::new(TSXTokenType::Semicolon, ";"),
+                TSXToken::new(TSXTokenType::Ident, "a"),
+                TSXToken::new(TSXTokenType::Slash, "/"),
+                TSXToken::new(TSXTokenType::NumberLiteral, "2"),
+                TSXToken::new(TSXTokenType::Slash, "/"),
+                TSXToken::new(TSXTokenType::Ident, "b"),
+                TSXToken::new(TSXTokenType::Semicolon, ";"),
+                TSXToken::new(TSXTokenType::If, "if"),
+                TSXToken::new(TSXTokenType::LParentheses, "("),
+                TSXToken::new(TSXTokenType::RegexLiteral, r"/[/'`]/"),
+                TSXToken::new(TSXTokenType::Dot, "."),
+                TSXToken::new(TSXTokenType::Ident, "test"),
+                TSXToken::new(TSXTokenType::LParentheses, "("),
+                TSXToken::new(TSXTokenType::Ident, "s"),
+                TSXToken::new(TSXTokenType::RParentheses, ")"),
+                TSXToken::new(TSXTokenType::RParentheses, ")"),
+                TSXToken::new(TSXTokenType::Return, "return"),
+                TSXToken::new(TSXTokenType::RegexLiteral, "/=/"),
+                TSXToken::new(TSXTokenType::Semicolon, ";"),
+            ]
+        );
+    }
+    #[test]
+    fn test_lexer_comments() {
+        let content = "/** a (b */ x\n/* d */ / 2 /* e";
+        let mut lexer = Lexer::new(content);
+        let mut tokens = Vec::new();
+        loop {
+            let token = lexer.next_token();
+            if token.token_type == TSXTokenType::Eof {
+                break;
+            }
+            tokens.push(token);
+        }
+        assert_eq!(
+            tokens,
+            vec![
+                TSXToken::new(TSXTokenType::Comment, "/** a (b */"),
+                TSXToken::new(TSXTokenType::Ident, "x"),
+                TSXToken::new(TSXTokenType::Comment, "/* d */"),
+                TSXToken::new(TSXTokenType::Slash, "/"),
+                TSXToken::new(TSXTokenType::NumberLiteral, "2"),
+                TSXToken::new(TSXTokenType::Comment, "/* e"),
+            ]
+        );
+    }
+    #[test]
+    fn test_lexer_numbers() {
+        let content = "1_000 0xff 0O17 0b1010 1.5e3 1e-3 .5 10n 1em x.y";
+        let mut lexer = Lexer::new(content);
+        let mut tokens = Vec::new();
+        loop {
+            let token = lexer.next_token();
+            if token.token_type == TSXTokenType::Eof {
+                break;
+            }
+            tokens.push(token);
+        }
+        assert_eq!(
+            tokens,
+            vec![
+                TSXToken::new(TSXTokenType::NumberLiteral, "1_000"),
+                TSXToken::new(TSXTokenType::NumberLiteral, "0xff"),
+                TSXToken::new(TSXTokenType::NumberLiteral, "0O17"),
+                TSXToken::new(TSXTokenType::NumberLiteral, "0b1010"),
+                TSXToken::new(TSXTokenType::NumberLiteral, "1.5e3"),
+                TSXToken::new(TSXTokenType::NumberLiteral, "1e-3"),
+                TSXToken::new(TSXTokenType::NumberLiteral, ".5"),
+                TSXToken::new(TSXTokenType::NumberLiteral, "10n"),
+                TSXToken::new(TSXTokenType::NumberLiteral, "1"),
+                TSXToken::new(TSXTokenType::Ident, "em"),
+                TSXToken::new(TSXTokenType::Ident, "x"),
+                TSXToken::new(TSXTokenType::Dot, "."),
+                TSXToken::new(TSXTokenType::Ident, "y"),
+            ]
+        );
+    }
 }
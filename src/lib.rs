@@ -3,16 +3,31 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use component::Component;
+use cli::{ActionStyle, StoriesFormat};
+use component::{property_key, Component, SampleOptions};
 pub mod cli;
 mod component;
+mod evaluator;
 mod lexer;
 mod parser;
+mod symbol;
+#[cfg(test)]
+mod test_helper;
 mod token;
+mod tsconfig;
 
 struct StoryBookContent {
     title: String,
     component: Component,
+    // other components of the same file, listed in the same stories file
+    subcomponents: Vec<Component>,
+    format: StoriesFormat,
+    // write a story for every literal member of union props
+    variants: bool,
+    // None follows the format
+    actions: Option<ActionStyle>,
+    // leave optional props out of Primary args
+    omit_optional: bool,
 }
 
 impl StoryBookContent {
@@ -20,60 +35,374 @@ impl StoryBookContent {
         Self {
             title: title.into(),
             component,
+            subcomponents: Vec::new(),
+            format: StoriesFormat::Csf2,
+            variants: false,
+            actions: None,
+            omit_optional: false,
         }
     }
-    fn import_component(&self, path: &str) -> String {
-        format!(r#"import {{ {} }} from "./{}";"#, self.component.name, path)
+    fn with_subcomponents(mut self, subcomponents: Vec<Component>) -> Self {
+        self.subcomponents = subcomponents;
+        self
+    }
+    fn with_format(mut self, format: StoriesFormat) -> Self {
+        self.format = format;
+        self
+    }
+    fn with_variants(mut self, variants: bool) -> Self {
+        self.variants = variants;
+        self
+    }
+    fn with_actions(mut self, actions: Option<ActionStyle>) -> Self {
+        self.actions = actions;
+        self
+    }
+    // fn() needs @storybook/test which came with the CSF3 era
+    fn actions(&self) -> ActionStyle {
+        self.actions.unwrap_or(match self.format {
+            StoriesFormat::Csf2 => ActionStyle::Action,
+            StoriesFormat::Csf3 => ActionStyle::Fn,
+        })
+    }
+    fn with_omit_optional(mut self, omit_optional: bool) -> Self {
+        self.omit_optional = omit_optional;
+        self
+    }
+    fn sample_options(&self) -> SampleOptions {
+        SampleOptions::new(self.actions()).with_omit_optional(self.omit_optional)
     }
-    fn import_props(&self, path: &str) -> String {
-        match &self.component.props_name() {
-            Some(s) => format!(r#"import {{ {} }} from "./{}";"#, s, path),
-            None => "".to_string(),
+    fn import_actions(&self) -> &'static str {
+        if !self
+            .components()
+            .any(|component| component.has_function_props(&self.sample_options()))
+        {
+            return "";
+        }
+        match self.actions() {
+            ActionStyle::Fn => "\nimport { fn } from \"@storybook/test\";",
+            ActionStyle::Action => "\nimport { action } from \"@storybook/addon-actions\";",
+        }
+    }
+    fn components(&self) -> impl Iterator<Item = &Component> {
+        std::iter::once(&self.component).chain(self.subcomponents.iter())
+    }
+    // stories of subcomponents are prefixed by the component name
+    fn story_prefix<'a>(&self, component: &'a Component) -> &'a str {
+        if std::ptr::eq(component, &self.component) {
+            ""
+        } else {
+            component.name.as_str()
         }
     }
-    fn import_libraries(&self) -> &'static str {
-        r#"import React from "react";
-import { StoryFn } from "@storybook/react";"#
+    // module is where the stories import the component file from, ./Button or @/components/Button
+    fn import_component(&self, module: &str) -> String {
+        let mut imports = self
+            .components()
+            .filter(|component| component.is_default_export())
+            .map(|component| format!(r#"import {} from "{}";"#, component.name, module))
+            .collect::<Vec<_>>();
+        let names = self
+            .components()
+            .filter(|component| !component.is_default_export())
+            .map(|component| component.name.as_str())
+            .collect::<Vec<_>>();
+        if !names.is_empty() {
+            imports.push(format!(
+                r#"import {{ {} }} from "{}";"#,
+                names.join(", "),
+                module
+            ));
+        }
+        imports.join("\n")
+    }
+    // props types and enums used by the samples
+    fn import_props(&self, component_module: &str) -> String {
+        self.import_specifiers(
+            component_module,
+            self.components().flat_map(|component| {
+                component
+                    .props_import()
+                    .into_iter()
+                    .chain(component.enum_imports())
+            }),
+        )
+    }
+    // CSF3 stories are typed by the meta, only enums used by the samples are imported
+    fn import_enums(&self, component_module: &str) -> String {
+        self.import_specifiers(
+            component_module,
+            self.components()
+                .flat_map(|component| component.enum_imports()),
+        )
+    }
+    // (MODULE, SPECIFIER) -> import { SPECIFIER, SPECIFIER } from "MODULE"
+    fn import_specifiers<'a>(
+        &self,
+        component_module: &str,
+        specifiers: impl Iterator<Item = (Option<&'a str>, String)>,
+    ) -> String {
+        let mut imports: Vec<(String, Vec<String>)> = Vec::new();
+        for (module, specifier) in specifiers {
+            let module = module
+                .map(|module| module.to_string())
+                .unwrap_or_else(|| component_module.to_string());
+            match imports.iter_mut().find(|(m, _)| *m == module) {
+                Some((_, specifiers)) if specifiers.contains(&specifier) => {}
+                Some((_, specifiers)) => specifiers.push(specifier),
+                None => imports.push((module, vec![specifier])),
+            }
+        }
+        imports
+            .iter()
+            .map(|(module, specifiers)| {
+                format!(
+                    r#"import {{ {} }} from "{}";"#,
+                    specifiers.join(", "),
+                    module
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+    fn import_libraries(&self) -> String {
+        format!(
+            r#"import React from "react";
+import {{ StoryFn }} from "@storybook/react";{}"#,
+            self.import_actions()
+        )
+    }
+    fn subcomponents(&self) -> String {
+        if self.subcomponents.is_empty() {
+            return "".to_string();
+        }
+        format!(
+            "\n    subcomponents: {{ {} }},",
+            self.subcomponents
+                .iter()
+                .map(|component| component.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+    // controls of the component props
+    fn arg_types(&self) -> String {
+        let arg_types = self.component.arg_types();
+        if arg_types.is_empty() {
+            return "".to_string();
+        }
+        let arg_types = arg_types
+            .iter()
+            .map(|(key, arg_type)| {
+                format!("\n        {}: {},", property_key(key), arg_type.to_str(key))
+            })
+            .collect::<String>();
+        format!("\n    argTypes: {{{}\n    }},", arg_types)
     }
     fn export_default(&self) -> String {
         format!(
             r#"export default {{
     title: "{}",
-    component: {},
+    component: {},{}{}
 }};"#,
-            self.title, self.component.name
+            self.title,
+            self.component.name,
+            self.subcomponents(),
+            self.arg_types()
         )
     }
-    fn template(&self) -> String {
+    fn template(&self, component: &Component) -> String {
         format!(
-            r#"const Template: StoryFn<{}> = (args) => (
+            r#"const {}Template: StoryFn<{}> = (args) => (
   <{} {{...args}} />
 );"#,
-            self.component.props_str(),
-            self.component.name
+            self.story_prefix(component),
+            component.props_str(),
+            component.name
         )
     }
-    fn primary_sample(&self) -> String {
+    fn primary_sample(&self, component: &Component) -> String {
+        let prefix = self.story_prefix(component);
         format!(
-            r#"export const Primary = Template.bind({{}});
+            r#"export const {prefix}Primary = {prefix}Template.bind({{}});
 
-Primary.args = {};"#,
-            self.component.fill_sample()
+{prefix}Primary.args = {};"#,
+            component.fill_sample(&self.sample_options())
+        )
+    }
+    // (STORY NAME, KEY, LITERAL) for variant: "primary" | "secondary" -> Secondary
+    fn variant_stories(&self, component: &Component) -> Vec<(String, String, String)> {
+        if !self.variants {
+            return Vec::new();
+        }
+        let mut names = vec!["Primary".to_string()];
+        component
+            .variants()
+            .into_iter()
+            .map(|(key, literal)| {
+                let value = literal.trim_matches(|c| matches!(c, '"' | '\'' | '`'));
+                let mut name = match value.strip_prefix('-') {
+                    Some(value) => pascal_case(&format!("minus {}", value)),
+                    None => pascal_case(value),
+                };
+                // size: 1 -> Size1, and the same value in another prop
+                if !name.starts_with(char::is_alphabetic) || names.contains(&name) {
+                    name = format!("{}{}", pascal_case(&key), name);
+                }
+                let base = name.clone();
+                let mut count = 1;
+                while names.contains(&name) {
+                    count += 1;
+                    name = format!("{}{}", base, count);
+                }
+                names.push(name.clone());
+                (name, key, literal)
+            })
+            .collect()
+    }
+    fn variant_samples(&self, component: &Component) -> Vec<String> {
+        let prefix = self.story_prefix(component);
+        self.variant_stories(component)
+            .into_iter()
+            .map(|(name, key, literal)| {
+                format!(
+                    r#"export const {prefix}{name} = {prefix}Template.bind({{}});
+
+{prefix}{name}.args = {{ ...{prefix}Primary.args, {key}: {literal} }};"#,
+                    key = property_key(&key),
+                )
+            })
+            .collect()
+    }
+    // CSF3: stories are objects typed by the meta
+    fn import_csf3_libraries(&self) -> String {
+        // subcomponent stories render JSX
+        let react = if self.subcomponents.is_empty() {
+            ""
+        } else {
+            "import React from \"react\";\n"
+        };
+        format!(
+            r#"{}import type {{ Meta, StoryObj }} from "@storybook/react";{}"#,
+            react,
+            self.import_actions()
+        )
+    }
+    fn meta(&self) -> String {
+        format!(
+            r#"const meta: Meta<typeof {component}> = {{
+    title: "{}",
+    component: {component},{}{}
+}};
+export default meta;
+type Story = StoryObj<typeof meta>;"#,
+            self.title,
+            self.subcomponents(),
+            self.arg_types(),
+            component = self.component.name,
+        )
+    }
+    fn story_obj(&self, component: &Component) -> String {
+        let prefix = self.story_prefix(component);
+        if prefix.is_empty() {
+            return format!(
+                r#"export const Primary: Story = {{
+    args: {},
+}};"#,
+                component.fill_sample(&self.sample_options())
+            );
+        }
+        // StoryObj<typeof meta> is for the main component only
+        format!(
+            r#"export const {prefix}Primary: StoryObj<typeof {name}> = {{
+    render: (args) => <{name} {{...args}} />,
+    args: {},
+}};"#,
+            component.fill_sample(&self.sample_options()),
+            name = component.name,
+        )
+    }
+    fn variant_story_objs(&self, component: &Component) -> Vec<String> {
+        let prefix = self.story_prefix(component);
+        let story = if prefix.is_empty() {
+            "Story".to_string()
+        } else {
+            format!("StoryObj<typeof {}>", component.name)
+        };
+        self.variant_stories(component)
+            .into_iter()
+            .map(|(name, key, literal)| {
+                format!(
+                    r#"export const {prefix}{name}: {story} = {{
+    ...{prefix}Primary,
+    args: {{ ...{prefix}Primary.args, {key}: {literal} }},
+}};"#,
+                    key = property_key(&key),
+                )
+            })
+            .collect()
+    }
+    fn to_file_content(&self, module: &str) -> String {
+        match self.format {
+            StoriesFormat::Csf2 => self.to_csf2_content(module),
+            StoriesFormat::Csf3 => self.to_csf3_content(module),
+        }
+    }
+    fn to_csf3_content(&self, module: &str) -> String {
+        let stories = self
+            .components()
+            .flat_map(|component| {
+                std::iter::once(self.story_obj(component)).chain(self.variant_story_objs(component))
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let mut imports = self.import_component(module);
+        let enums = self.import_enums(module);
+        if !enums.is_empty() {
+            imports.push('\n');
+            imports.push_str(&enums);
+        }
+        format!(
+            "{}\n{}\n\n{}\n\n{}\n",
+            self.import_csf3_libraries(),
+            imports,
+            self.meta(),
+            stories
         )
     }
-    fn to_file_content(&self, file_name: &str) -> String {
+    fn to_csf2_content(&self, module: &str) -> String {
+        let stories = self
+            .components()
+            .flat_map(|component| {
+                [self.template(component), self.primary_sample(component)]
+                    .into_iter()
+                    .chain(self.variant_samples(component))
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
         format!(
-            "{}\n{}\n{}\n{}\n\n{}\n\n{}\n",
+            "{}\n{}\n{}\n{}\n\n{}\n",
             self.import_libraries(),
-            self.import_component(file_name),
-            self.import_props(file_name),
+            self.import_component(module),
+            self.import_props(module),
             self.export_default(),
-            self.template(),
-            self.primary_sample()
+            stories
         )
     }
 }
 
+// "x-large" -> XLarge
+fn pascal_case(s: &str) -> String {
+    s.split(|c: char| !c.is_alphanumeric())
+        .filter_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+        })
+        .collect()
+}
+
 pub fn to_stories_path(path: impl AsRef<Path>) -> PathBuf {
     let filename = path
         .as_ref()
@@ -90,6 +419,11 @@ pub fn to_stories_path(path: impl AsRef<Path>) -> PathBuf {
         .into()
 }
 
+pub fn to_component_stories_path(path: impl AsRef<Path>, component_name: &str) -> PathBuf {
+    path.as_ref()
+        .with_file_name(format!("{}.stories.tsx", component_name))
+}
+
 fn is_stories(path: impl AsRef<Path>) -> bool {
     let Some(Some(mut split)) = path
         .as_ref()
@@ -99,7 +433,7 @@ fn is_stories(path: impl AsRef<Path>) -> bool {
         return false;
     };
     if let (Some(name), Some(ext)) = (split.next(), split.next()) {
-        return ext == "stories" && name.len() > 0;
+        return ext == "stories" && !name.is_empty();
     };
     false
 }
@@ -142,25 +476,19 @@ fn all_file_path(root: impl AsRef<Path>) -> Result<Vec<PathBuf>, std::io::Error>
 }
 
 #[cfg(test)]
-
 mod tests {
-    use crate::component::{Key, NamedProps, ObjectType, PrimitiveType, Props, Type};
+    use crate::{
+        component::{
+            EnumType, Key, LiteralType, NamedProps, ObjectType, PrimitiveType, Property, Props,
+            Type,
+        },
+        symbol::Import,
+        test_helper::{create_dir_all, create_file, remove_dir},
+    };
 
     use super::*;
-    use std::path::{Path, PathBuf};
+    use std::path::PathBuf;
 
-    fn create_dir_all(dir_name: &str) {
-        std::fs::create_dir_all(dir_name).unwrap();
-    }
-    fn create_file(file_name: &str, content: &str) {
-        std::fs::write(file_name, content).unwrap();
-    }
-    fn remove_dir(dir_name: &str) {
-        let path: &Path = dir_name.as_ref();
-        if path.exists() {
-            std::fs::remove_dir_all(dir_name).unwrap();
-        }
-    }
     #[test]
     fn test_make_storybook_content() {
         let mut props = ObjectType::new();
@@ -178,7 +506,7 @@ mod tests {
 
         let storybook_content = StoryBookContent::new("Sample/ErrorAlert", component);
         assert_eq!(
-            storybook_content.to_file_content("ErrorAlert"),
+            storybook_content.to_file_content("./ErrorAlert"),
             r#"import React from "react";
 import { StoryFn } from "@storybook/react";
 import { ErrorAlert } from "./ErrorAlert";
@@ -186,6 +514,9 @@ import { Props } from "./ErrorAlert";
 export default {
     title: "Sample/ErrorAlert",
     component: ErrorAlert,
+    argTypes: {
+        timeOut: { control: "number" },
+    },
 };
 
 const Template: StoryFn<Props> = (args) => (
@@ -199,6 +530,400 @@ Primary.args = { errorMessage: "",timeOut: 0, };
         );
     }
     #[test]
+    fn test_make_storybook_content_with_subcomponents() {
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("title".to_string()),
+            Type::Primitive(PrimitiveType::String),
+        );
+        let card = Component::new("Card", Props::Named(NamedProps::new("CardProps", props)));
+        let card_header = Component::new("CardHeader", Props::Expand(ObjectType::new()));
+        let card_footer =
+            Component::new("CardFooter", Props::Expand(ObjectType::new())).default_export();
+
+        let storybook_content = StoryBookContent::new("Sample/Card", card)
+            .with_subcomponents(vec![card_header, card_footer]);
+        assert_eq!(
+            storybook_content.to_file_content("./Card"),
+            r#"import React from "react";
+import { StoryFn } from "@storybook/react";
+import CardFooter from "./Card";
+import { Card, CardHeader } from "./Card";
+import { CardProps } from "./Card";
+export default {
+    title: "Sample/Card",
+    component: Card,
+    subcomponents: { CardHeader, CardFooter },
+};
+
+const Template: StoryFn<CardProps> = (args) => (
+  <Card {...args} />
+);
+
+export const Primary = Template.bind({});
+
+Primary.args = { title: "", };
+
+const CardHeaderTemplate: StoryFn<{  }> = (args) => (
+  <CardHeader {...args} />
+);
+
+export const CardHeaderPrimary = CardHeaderTemplate.bind({});
+
+CardHeaderPrimary.args = {  };
+
+const CardFooterTemplate: StoryFn<{  }> = (args) => (
+  <CardFooter {...args} />
+);
+
+export const CardFooterPrimary = CardFooterTemplate.bind({});
+
+CardFooterPrimary.args = {  };
+"#
+        );
+    }
+    #[test]
+    fn test_make_csf3_storybook_content() {
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("title".to_string()),
+            Type::Primitive(PrimitiveType::String),
+        );
+        let card = Component::new("Card", Props::Named(NamedProps::new("CardProps", props)));
+        let card_header = Component::new("CardHeader", Props::Expand(ObjectType::new()));
+
+        let storybook_content =
+            StoryBookContent::new("Sample/Card", card.clone()).with_format(StoriesFormat::Csf3);
+        assert_eq!(
+            storybook_content.to_file_content("@/components/Card"),
+            r#"import type { Meta, StoryObj } from "@storybook/react";
+import { Card } from "@/components/Card";
+
+const meta: Meta<typeof Card> = {
+    title: "Sample/Card",
+    component: Card,
+};
+export default meta;
+type Story = StoryObj<typeof meta>;
+
+export const Primary: Story = {
+    args: { title: "", },
+};
+"#
+        );
+
+        let storybook_content = StoryBookContent::new("Sample/Card", card)
+            .with_subcomponents(vec![card_header])
+            .with_format(StoriesFormat::Csf3);
+        assert_eq!(
+            storybook_content.to_file_content("./Card"),
+            r#"import React from "react";
+import type { Meta, StoryObj } from "@storybook/react";
+import { Card, CardHeader } from "./Card";
+
+const meta: Meta<typeof Card> = {
+    title: "Sample/Card",
+    component: Card,
+    subcomponents: { CardHeader },
+};
+export default meta;
+type Story = StoryObj<typeof meta>;
+
+export const Primary: Story = {
+    args: { title: "", },
+};
+
+export const CardHeaderPrimary: StoryObj<typeof CardHeader> = {
+    render: (args) => <CardHeader {...args} />,
+    args: {  },
+};
+"#
+        );
+    }
+    #[test]
+    fn test_make_variant_stories() {
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("variant".to_string()),
+            Type::Union(vec![
+                Type::Literal(LiteralType::String("\"primary\"".to_string())),
+                Type::Literal(LiteralType::String("\"secondary\"".to_string())),
+                Type::Literal(LiteralType::String("\"x-large\"".to_string())),
+            ]),
+        );
+        props.insert_property(
+            Key("size".to_string()),
+            Property::new(Type::Union(vec![
+                Type::Literal(LiteralType::Number("1".to_string())),
+                Type::Literal(LiteralType::Number("2".to_string())),
+            ]))
+            .optional(),
+        );
+        let button = Component::new("Button", Props::Named(NamedProps::new("Props", props)));
+
+        let storybook_content =
+            StoryBookContent::new("Sample/Button", button.clone()).with_variants(true);
+        assert_eq!(
+            storybook_content.to_file_content("./Button"),
+            r#"import React from "react";
+import { StoryFn } from "@storybook/react";
+import { Button } from "./Button";
+import { Props } from "./Button";
+export default {
+    title: "Sample/Button",
+    component: Button,
+    argTypes: {
+        size: { control: "radio", options: [1, 2] },
+        variant: { control: "radio", options: ["primary", "secondary", "x-large"] },
+    },
+};
+
+const Template: StoryFn<Props> = (args) => (
+  <Button {...args} />
+);
+
+export const Primary = Template.bind({});
+
+Primary.args = { size: 1,variant: "primary", };
+
+export const Size2 = Template.bind({});
+
+Size2.args = { ...Primary.args, size: 2 };
+
+export const Secondary = Template.bind({});
+
+Secondary.args = { ...Primary.args, variant: "secondary" };
+
+export const XLarge = Template.bind({});
+
+XLarge.args = { ...Primary.args, variant: "x-large" };
+"#
+        );
+
+        let storybook_content = StoryBookContent::new("Sample/Button", button)
+            .with_format(StoriesFormat::Csf3)
+            .with_variants(true);
+        assert!(storybook_content.to_file_content("./Button").ends_with(
+            r#"export const Secondary: Story = {
+    ...Primary,
+    args: { ...Primary.args, variant: "secondary" },
+};
+
+export const XLarge: Story = {
+    ...Primary,
+    args: { ...Primary.args, variant: "x-large" },
+};
+"#
+        ));
+    }
+    #[test]
+    fn test_make_nullable_variant_stories() {
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("label".to_string()),
+            Type::Union(vec![
+                Type::Primitive(PrimitiveType::Null),
+                Type::Primitive(PrimitiveType::String),
+            ]),
+        );
+        props.insert(
+            Key("data".to_string()),
+            Type::Primitive(PrimitiveType::Unknown),
+        );
+        let badge = Component::new("Badge", Props::Named(NamedProps::new("Props", props)));
+
+        let storybook_content = StoryBookContent::new("Sample/Badge", badge).with_variants(true);
+        assert!(storybook_content.to_file_content("./Badge").ends_with(
+            r#"Primary.args = { data: undefined,label: "", };
+
+export const Null = Template.bind({});
+
+Null.args = { ...Primary.args, label: null };
+"#
+        ));
+    }
+    #[test]
+    fn test_make_quoted_key_variant_stories() {
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("aria-pressed".to_string()),
+            Type::Union(vec![
+                Type::Literal(LiteralType::String("\"true\"".to_string())),
+                Type::Literal(LiteralType::String("\"false\"".to_string())),
+            ]),
+        );
+        let toggle = Component::new("Toggle", Props::Expand(props));
+
+        let storybook_content = StoryBookContent::new("Sample/Toggle", toggle).with_variants(true);
+        assert!(storybook_content.to_file_content("./Toggle").ends_with(
+            r#"False.args = { ...Primary.args, "aria-pressed": "false" };
+"#
+        ));
+        let storybook_content = storybook_content.with_format(StoriesFormat::Csf3);
+        assert!(storybook_content
+            .to_file_content("./Toggle")
+            .contains(r#"    args: { ...Primary.args, "aria-pressed": "false" },"#));
+    }
+    #[test]
+    fn test_arg_types() {
+        let mut props = ObjectType::new();
+        props.insert_property(
+            Key("disabled".to_string()),
+            Property::new(Type::Primitive(PrimitiveType::Boolean)).optional(),
+        );
+        props.insert(
+            Key("color".to_string()),
+            Type::Union(vec![
+                Type::Literal(LiteralType::String("\"red\"".to_string())),
+                Type::Literal(LiteralType::String("\"green\"".to_string())),
+                Type::Literal(LiteralType::String("\"blue\"".to_string())),
+                Type::Literal(LiteralType::String("\"black\"".to_string())),
+                Type::Primitive(PrimitiveType::Undefined),
+            ]),
+        );
+        props.insert(
+            Key("aria-label".to_string()),
+            Type::Primitive(PrimitiveType::String),
+        );
+        props.insert(Key("style".to_string()), Type::Object(ObjectType::new()));
+        props.insert(
+            Key("items".to_string()),
+            Type::Array(Box::new(Type::Primitive(PrimitiveType::String))),
+        );
+        props.insert(
+            Key("onClick".to_string()),
+            Type::Function {
+                params: Vec::new(),
+                ret: Box::new(Type::Primitive(PrimitiveType::Void)),
+            },
+        );
+        let button = Component::new("Button", Props::Expand(props));
+
+        let storybook_content = StoryBookContent::new("Sample/Button", button);
+        assert_eq!(
+            storybook_content.arg_types(),
+            r#"
+    argTypes: {
+        color: { control: "select", options: ["red", "green", "blue", "black"] },
+        disabled: { control: "boolean" },
+        items: { control: "object" },
+        onClick: { action: "onClick" },
+        style: { control: "object" },
+    },"#
+        );
+    }
+    #[test]
+    fn test_make_storybook_content_with_actions() {
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("onClick".to_string()),
+            Type::Function {
+                params: Vec::new(),
+                ret: Box::new(Type::Primitive(PrimitiveType::Void)),
+            },
+        );
+        let button = Component::new("Button", Props::Expand(props));
+
+        let storybook_content =
+            StoryBookContent::new("Sample/Button", button.clone()).with_format(StoriesFormat::Csf3);
+        assert_eq!(
+            storybook_content.to_file_content("./Button"),
+            r#"import type { Meta, StoryObj } from "@storybook/react";
+import { fn } from "@storybook/test";
+import { Button } from "./Button";
+
+const meta: Meta<typeof Button> = {
+    title: "Sample/Button",
+    component: Button,
+    argTypes: {
+        onClick: { action: "onClick" },
+    },
+};
+export default meta;
+type Story = StoryObj<typeof meta>;
+
+export const Primary: Story = {
+    args: { onClick: fn(), },
+};
+"#
+        );
+        let storybook_content = StoryBookContent::new("Sample/Button", button);
+        assert_eq!(
+            storybook_content.import_libraries(),
+            r#"import React from "react";
+import { StoryFn } from "@storybook/react";
+import { action } from "@storybook/addon-actions";"#
+        );
+        assert_eq!(
+            storybook_content.primary_sample(&storybook_content.component),
+            r#"export const Primary = Template.bind({});
+
+Primary.args = { onClick: action("onClick"), };"#
+        );
+    }
+    #[test]
+    fn test_import_props_from_other_module() {
+        let button = Component::new(
+            "Button",
+            Props::Named(
+                NamedProps::new("ButtonProps", ObjectType::new())
+                    .imported("ButtonProps", Import::new("ButtonProps", "./types")),
+            ),
+        );
+        let card = Component::new(
+            "Card",
+            Props::Named(
+                NamedProps::new("CardProps", ObjectType::new())
+                    .imported("CardProps", Import::new("BaseCardProps", "./types")),
+            ),
+        );
+        let icon = Component::new(
+            "Icon",
+            Props::Named(NamedProps::new("IconProps", ObjectType::new())),
+        );
+        let storybook_content =
+            StoryBookContent::new("Sample/Button", button).with_subcomponents(vec![card, icon]);
+        assert_eq!(
+            storybook_content.import_props("./Button"),
+            r#"import { ButtonProps, BaseCardProps as CardProps } from "./types";
+import { IconProps } from "./Button";"#
+        );
+    }
+    #[test]
+    fn test_import_enums_of_samples() {
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("size".to_string()),
+            Type::Enum(EnumType::new("ButtonSize", vec!["Small".to_string()])),
+        );
+        props.insert(
+            Key("icon".to_string()),
+            Type::Enum(
+                EnumType::new("Icon", vec!["Add".to_string()])
+                    .imported("Icon", Import::new("Icon", "./icons")),
+            ),
+        );
+        let button = Component::new("Button", Props::Named(NamedProps::new("Props", props)));
+        let storybook_content = StoryBookContent::new("Sample/Button", button);
+        assert_eq!(
+            storybook_content.import_props("./Button"),
+            r#"import { Props, ButtonSize } from "./Button";
+import { Icon } from "./icons";"#
+        );
+        assert_eq!(
+            storybook_content.import_enums("./Button"),
+            r#"import { Icon } from "./icons";
+import { ButtonSize } from "./Button";"#
+        );
+    }
+    #[test]
+    fn test_to_component_stories_path() {
+        assert_eq!(
+            to_component_stories_path("src/components/Card.tsx", "CardHeader"),
+            PathBuf::from("src/components/CardHeader.stories.tsx")
+        );
+    }
+    #[test]
     fn test_is_stories() {
         let dir_name = "test_all_file_path";
         assert!(is_stories("test.stories.tsx"));
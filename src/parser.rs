@@ -1,508 +1,1138 @@
-use std::collections::HashMap;
+use std::{
+    convert::Infallible,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
 
 use crate::{
-    component::{Component, Key, NamedProps, ObjectType, Props, TSXContent, Type},
+    component::{
+        Component, EnumType, Key, LiteralType, NamedProps, ObjectType, Param, PrimitiveType,
+        Property, Props, TSXContent, TupleElement, Type,
+    },
+    evaluator::Evaluator,
     lexer::Lexer,
-    token::{TSXToken, TSXTokenType},
+    symbol::{Import, ModuleLoader, SymbolTable},
+    token::{Position, TSXToken, TSXTokenType},
+    tsconfig::TsConfig,
 };
 
-type TypeName = String;
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ParseError {
+    file: Option<PathBuf>,
+    position: Position,
+    message: String,
+}
+impl ParseError {
+    fn unexpected(token: &TSXToken) -> Self {
+        let message = if token.token_type == TSXTokenType::Eof {
+            "unexpected end of file".to_string()
+        } else {
+            format!("unexpected `{}`", token.literal)
+        };
+        Self {
+            file: None,
+            position: token.span.start,
+            message,
+        }
+    }
+    // importされたファイルで起きたエラーは既にそのファイルを持っている
+    pub fn with_file(mut self, file: impl Into<PathBuf>) -> Self {
+        if self.file.is_none() {
+            self.file = Some(file.into());
+        }
+        self
+    }
+}
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+        }
+        write!(
+            f,
+            "{}:{}: {}",
+            self.position.line, self.position.column, self.message
+        )
+    }
+}
+impl std::error::Error for ParseError {}
 
 pub(super) struct ComponentPartsParser<'a> {
     lexer: Lexer<'a>,
+    // literal types are kept as written in the source
+    source: &'a str,
+    path: Option<&'a Path>,
+    tsconfig: Option<&'a TsConfig>,
     // TSXContent内の型情報を全て確保しておくもの
-    type_buffer: HashMap<TypeName, Props>,
+    // componentのpropsは全ての宣言を読み終えてからここを引いて解決する
+    symbols: SymbolTable,
     peek: Option<TSXToken>,
 }
 
 // 1. typeを探す
 // 2. componentを探す
 // 3. componentのpropsを探す
-// 4. propsの型を探す
+// 4. 全て読み終えてからpropsの型をsymbolsで解決する
+//    symbolsに無ければimportされたファイルから探す
 
 impl ComponentPartsParser<'_> {
-    pub fn new(content: &TSXContent) -> ComponentPartsParser {
-        let lexer = Lexer::new(&content.0);
-        let type_buffer = HashMap::new();
+    pub fn new(content: &TSXContent) -> ComponentPartsParser<'_> {
+        let lexer = Lexer::new(content.source());
         ComponentPartsParser {
             lexer,
-            type_buffer,
+            source: content.source(),
+            path: content.path(),
+            tsconfig: content.tsconfig(),
+            symbols: SymbolTable::new(),
             peek: None,
         }
     }
-    // TODO: export されているcomponentの名前しか見つけてない
-    pub fn search_component(&mut self) -> Option<Component> {
+    // export されているcomponentを全て探す
+    // 型の宣言がcomponentより後にあっても解決できるよう,
+    // 先にファイル全体を読んでからpropsを解決する
+    // import されていて parse できなかったファイルのエラーも返す
+    pub fn search_components(&mut self) -> Result<(Vec<Component>, Vec<ParseError>), ParseError> {
+        let components = self.read_declarations()?;
+        let mut loader = ModuleLoader::new(self.tsconfig);
+        let components = components
+            .into_iter()
+            .map(|component| self.resolve_props(component, &mut loader))
+            .collect();
+        Ok((components, loader.into_skipped()))
+    }
+    // ファイル内の宣言だけを読む
+    pub fn read_symbols(mut self) -> Result<SymbolTable, ParseError> {
+        self.read_declarations()?;
+        Ok(self.symbols)
+    }
+    fn resolve_props(&mut self, component: Component, loader: &mut ModuleLoader) -> Component {
+        let mut component = self.resolve_props_type(component, loader);
+        let (symbols, path) = (&self.symbols, self.path);
+        // Omit<BaseProps, "onClick"> や BaseProps を宣言されている型に展開する
+        let mut evaluator = Evaluator::new(|name: &str| {
+            let props = match symbols.get_type(name) {
+                Some(props) => Some(props.clone()),
+                None => match (path, symbols.get_import(name)) {
+                    (Some(path), Some(import)) => {
+                        loader.find_exported_type(path, &import.module, &import.name)
+                    }
+                    _ => None,
+                },
+            };
+            Ok::<_, Infallible>(props.map(|props| match props {
+                Props::Named(props) => props.inner().clone(),
+                Props::Expand(props) => Type::Object(props),
+            }))
+        });
+        let Ok(()) = component.evaluate_props(|ty| evaluator.evaluate(ty));
+        // size: ButtonSize の ButtonSize が enum であれば enum として扱う
+        let Ok(()) = component.resolve_aliases(&mut |name| {
+            if let Some(enum_type) = symbols.get_enum(name) {
+                return Ok::<_, Infallible>(Some(Type::Enum(enum_type.clone())));
+            }
+            let (Some(path), Some(import)) = (path, symbols.get_import(name)) else {
+                return Ok(None);
+            };
+            Ok(loader
+                .find_exported_enum(path, &import.module, &import.name)
+                .map(|enum_type| Type::Enum(enum_type.imported(name, import.clone()))))
+        });
+        component
+    }
+    fn resolve_props_type(&mut self, component: Component, loader: &mut ModuleLoader) -> Component {
+        let Some(props_name) = component.props_name().map(|name| name.to_string()) else {
+            return component;
+        };
+        if let Some(props) = self.symbols.get_type(&props_name) {
+            return component.with_props(props.clone());
+        }
+        let (Some(path), Some(import)) = (self.path, self.symbols.get_import(&props_name)) else {
+            return component;
+        };
+        let import = import.clone();
+        let Some(Props::Named(props)) =
+            loader.find_exported_type(path, &import.module, &import.name)
+        else {
+            return component;
+        };
+        let props = Props::Named(props.imported(props_name.as_str(), import));
+        self.symbols.insert_type(props_name, props.clone());
+        component.with_props(props)
+    }
+    // 型はsymbolsへ, componentはpropsの名前だけを持たせて返す
+    fn read_declarations(&mut self) -> Result<Vec<Component>, ParseError> {
+        let mut components = Vec::new();
+        // export されていない component の宣言
+        let mut locals: Vec<Component> = Vec::new();
+        // export { LOCAL as EXPORTED } の (LOCAL, EXPORTED), export default は EXPORTED が default
+        let mut exports: Vec<(String, String)> = Vec::new();
         loop {
-            let token = self.peek.take().unwrap_or_else(|| self.lexer.next_token());
+            let token = self.next_token();
             match token.token_type {
                 // type TypeName = { KEY:TYPE }
                 TSXTokenType::Type => {
-                    let type_name = self.lexer.next_token();
-                    assert_eq!(type_name.token_type, TSXTokenType::Ident);
-                    let assign = self.lexer.next_token();
-                    assert_eq!(assign.token_type, TSXTokenType::Assign);
-                    let next = self.lexer.next_token();
-                    match next.token_type {
-                        TSXTokenType::LCurlyBracket => {
-                            self.after_type_lcurl(type_name.literal.as_str());
-                        }
-                        _ => {}
-                    }
+                    let type_name = self.lex_token();
+                    self.after_type(type_name)?;
                 }
-                // export default function NAME(props:Props)
-                // export default const NAME = (props:Props) => {}
-                // export default const NAME:IDENT_TYPE<Type> = (props:Props) => {}
-                // export default const NAME = {}
-                // export default const NAME = "hoge"
-                // export default const NAME = 1
-                // export default NAME
+                // interface TypeName { KEY:TYPE }
+                // interface TypeName extends Base, Other<T> { KEY:TYPE }
+                TSXTokenType::Interface => {
+                    let type_name = self.expect(TSXTokenType::Ident)?;
+                    self.after_interface_name(&type_name)?;
+                }
+                // enum NAME { MEMBER, MEMBER = VALUE }
+                // const enum NAME { MEMBER }
+                TSXTokenType::Enum => {
+                    self.after_enum()?;
+                }
+                // export function NAME(props:Props) {}
+                // export default function NAME(props:Props) {}
+                // export const NAME = (props:Props) => {}
+                // export const NAME:IDENT_TYPE<Type> = (props:Props) => {}
                 // export const NAME = (props:{key:value,...}) => {}
                 // export const NAME = Value
+                // export default NAME
                 // export type
+                // export interface
+                // export enum, export const enum
+                // export { NAME as EXPORTED } from "MODULE"
+                // export type { NAME } from "MODULE"
+                // export * from "MODULE"
                 TSXTokenType::Export => {
-                    let default_or_const_type = self.lexer.next_token();
-                    if default_or_const_type.token_type == TSXTokenType::Type {
-                        self.peek = Some(default_or_const_type);
+                    let mut next = self.lex_token();
+                    if next.token_type == TSXTokenType::Interface
+                        || next.token_type == TSXTokenType::Enum
+                    {
+                        self.peek = Some(next);
                         continue;
                     }
-                    let function_or_const_or_name = self.lexer.next_token();
-                    match function_or_const_or_name.token_type {
-                        TSXTokenType::Fn => {
-                            let name = self.lexer.next_token();
-                            return self.from_props_lparen(name.literal.as_str());
+                    if next.token_type == TSXTokenType::Type {
+                        let type_name_or_lcurl = self.lex_token();
+                        if type_name_or_lcurl.token_type != TSXTokenType::LCurlyBracket {
+                            self.after_type(type_name_or_lcurl)?;
+                            continue;
+                        }
+                        next = type_name_or_lcurl;
+                    }
+                    let is_default = next.token_type == TSXTokenType::Default;
+                    if is_default {
+                        next = self.lex_token();
+                    }
+                    let component = match next.token_type {
+                        TSXTokenType::Fn
+                        | TSXTokenType::Const
+                        | TSXTokenType::Let
+                        | TSXTokenType::Var => self.after_declaration_keyword(next)?,
+                        // export default NAME
+                        TSXTokenType::Ident if is_default => {
+                            exports.push((next.literal, "default".to_string()));
+                            None
+                        }
+                        TSXTokenType::LCurlyBracket => {
+                            let specifiers = self.read_specifiers()?;
+                            match self.read_from_module()? {
+                                Some(module) => {
+                                    for (name, exported) in specifiers {
+                                        self.symbols
+                                            .insert_reexport(exported, Import::new(name, &module));
+                                    }
+                                }
+                                // export { NAME, NAME as EXPORTED }
+                                None => exports.extend(specifiers),
+                            }
+                            None
                         }
-                        TSXTokenType::Const => {
-                            let name = self.lexer.next_token();
-                            if let Some(component) = self.after_const_name(name.literal.as_str()) {
-                                return Some(component);
-                            };
-                            break;
+                        TSXTokenType::Asterisk => {
+                            if let Some(module) = self.read_from_module()? {
+                                self.symbols.insert_star_reexport(module);
+                            }
+                            None
                         }
-                        TSXTokenType::Ident => {
-                            let name = function_or_const_or_name;
-                            if let Some(component) = self.after_const_name(name.literal.as_str()) {
-                                return Some(component);
-                            };
-                            break;
+                        _ => {
+                            self.peek = Some(next);
+                            None
                         }
-                        _ => {}
+                    };
+                    if let Some(component) = component {
+                        components.push(if is_default {
+                            component.default_export()
+                        } else {
+                            component
+                        });
+                    }
+                }
+                // function NAME(props:Props) {}
+                // const NAME = (props:Props) => {}
+                // export default NAME や export { NAME } で後から export されるかもしれない
+                TSXTokenType::Fn | TSXTokenType::Const | TSXTokenType::Let | TSXTokenType::Var => {
+                    if let Some(component) = self.after_declaration_keyword(token)? {
+                        locals.push(component);
                     }
                 }
+                // import { NAME, NAME as LOCAL } from "MODULE"
+                // import type { NAME } from "MODULE"
+                // import DEFAULT, { NAME } from "MODULE"
+                TSXTokenType::Import => {
+                    self.after_import()?;
+                }
+                // 関数の中身や式は宣言ではないので読み飛ばす
+                TSXTokenType::LCurlyBracket
+                | TSXTokenType::LParentheses
+                | TSXTokenType::LBracket => {
+                    self.skip_block();
+                }
+                // export const NAME = () => <div />
+                TSXTokenType::LTag if self.lexer.is_jsx_start() => {
+                    self.lexer.skip_jsx_element();
+                }
                 TSXTokenType::Eof => {
                     break;
                 }
                 _ => {}
             }
         }
-        None
-    }
-    // :を取得したタイミングで利用する
-    fn get_type_value(&mut self, ltag_num: usize) -> Type {
-        fn add_after_type_literal(this: &mut ComponentPartsParser, token: &mut TSXToken) {
-            let mut next = this.lexer.next_token();
-            while next.token_type != TSXTokenType::Semicolon
-                && next.token_type != TSXTokenType::RCurlyBracket
-                && next.token_type != TSXTokenType::Comma
-            {
-                token.literal.push_str(&next.literal);
-                next = this.lexer.next_token();
+        // export されていなかった宣言を export された名前で component にする
+        for (local, exported) in exports {
+            let Some(component) = locals.iter().find(|component| component.name == local) else {
+                continue;
+            };
+            let mut component = component.clone();
+            if exported == "default" {
+                component = component.default_export();
+            } else {
+                component.name = exported;
             }
+            components.push(component);
         }
-        fn case_lbracket(this: &mut ComponentPartsParser, r#type: Type) -> Type {
-            let mut array_num = 1;
-            let rbracket = this.lexer.next_token();
-            assert_eq!(rbracket.token_type, TSXTokenType::RBracket);
-            this.peek = Some(this.lexer.next_token());
-
-            while let Some(TSXTokenType::LBracket) =
-                this.peek.as_ref().map(|t| t.token_type.clone())
-            {
-                let rbracket = this.lexer.next_token();
-                assert_eq!(rbracket.token_type, TSXTokenType::RBracket);
-                this.peek = Some(this.lexer.next_token());
-                array_num += 1;
+        Ok(components)
+    }
+    // function, const, let, var を取得したタイミングで利用する
+    // function NAME<T>(props:Props<T>) {}
+    // const NAME = <T,>(props:Props<T>) => {}
+    // component でなければ None
+    fn after_declaration_keyword(
+        &mut self,
+        keyword: TSXToken,
+    ) -> Result<Option<Component>, ParseError> {
+        let name = self.lex_token();
+        if keyword.token_type == TSXTokenType::Const && name.token_type == TSXTokenType::Enum {
+            self.peek = Some(name);
+            return Ok(None);
+        }
+        if !Self::is_component_name(&name) {
+            return Ok(None);
+        }
+        let component = if keyword.token_type == TSXTokenType::Fn {
+            let type_params = if self.peek_token_type() == TSXTokenType::LTag {
+                self.read_type_parameters()?
+            } else {
+                Vec::new()
+            };
+            if self.peek_token_type() != TSXTokenType::LParentheses {
+                return Ok(None);
             }
-            let mut type_value = r#type;
-            for _ in 0..array_num {
-                type_value = Type::Array(Box::new(type_value));
+            self.next_token();
+            self.after_props_lparen(name.literal.as_str(), &type_params)?
+        } else {
+            self.after_const_name(name.literal.as_str())?
+        };
+        Ok(component.map(|component| component.with_span(name.span)))
+    }
+    // type を取得したタイミングで利用する
+    // type NAME = { KEY:TYPE } & Base | Other<T>
+    // type NAME<T> = TYPE
+    fn after_type(&mut self, type_name: TSXToken) -> Result<(), ParseError> {
+        // <input type="text" /> や { type: "button" } は型宣言ではない
+        if type_name.token_type != TSXTokenType::Ident {
+            self.peek = Some(type_name);
+            return Ok(());
+        }
+        if self.peek_token_type() == TSXTokenType::LTag {
+            self.read_type_parameters()?;
+        }
+        self.expect(TSXTokenType::Assign)?;
+        let type_value = self.read_type()?;
+        self.symbols.insert_type(
+            type_name.literal.as_str(),
+            Props::Named(
+                NamedProps::new_type(type_name.literal.as_str(), type_value)
+                    .with_span(type_name.span),
+            ),
+        );
+        Ok(())
+    }
+    // { ( [ を取得したタイミングで利用する
+    // 対応する } ) ] まで読み飛ばす, 中の JSX は lexer に読み飛ばしてもらう
+    fn skip_block(&mut self) {
+        let mut depth = 1;
+        loop {
+            let token = self.next_token();
+            match token.token_type {
+                TSXTokenType::LCurlyBracket
+                | TSXTokenType::LParentheses
+                | TSXTokenType::LBracket => depth += 1,
+                TSXTokenType::RCurlyBracket
+                | TSXTokenType::RParentheses
+                | TSXTokenType::RBracket => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return;
+                    }
+                }
+                TSXTokenType::LTag if self.lexer.is_jsx_start() => self.lexer.skip_jsx_element(),
+                TSXTokenType::Eof => {
+                    self.peek = Some(token);
+                    return;
+                }
+                _ => {}
             }
-            match this.peek.as_ref().map(|t| t.token_type.clone()) {
-                Some(TSXTokenType::Semicolon) => {
-                    this.peek = Some(this.lexer.next_token());
+        }
+    }
+    // < を取得する前に利用する
+    // <T extends Base = Default, U> の T, U を返す, 制約や既定の型は使わないので読み飛ばす
+    fn read_type_parameters(&mut self) -> Result<Vec<String>, ParseError> {
+        let mut names = Vec::new();
+        let mut depth = 0;
+        // ( { [ の中の , は型引数の区切りではない
+        let mut nested = 0;
+        let mut at_name = false;
+        loop {
+            let token = self.next_token();
+            let is_name = at_name && nested == 0 && depth == 1;
+            at_name = false;
+            match token.token_type {
+                TSXTokenType::LTag => {
+                    depth += 1;
+                    at_name = depth == 1;
                 }
-                Some(TSXTokenType::Comma) => {
-                    this.peek = Some(this.lexer.next_token());
+                TSXTokenType::RTag => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(names);
+                    }
                 }
+                TSXTokenType::LCurlyBracket
+                | TSXTokenType::LParentheses
+                | TSXTokenType::LBracket => nested += 1,
+                TSXTokenType::RCurlyBracket
+                | TSXTokenType::RParentheses
+                | TSXTokenType::RBracket => nested -= 1,
+                TSXTokenType::Comma => at_name = true,
+                TSXTokenType::Ident if is_name => names.push(token.literal),
+                TSXTokenType::Eof => return Err(ParseError::unexpected(&token)),
                 _ => {}
             }
-            type_value
         }
-
-        // key: type_value_token
-        let mut type_value_token = self.lexer.next_token();
-        println!("type_value_token: {:?}", type_value_token);
-        match type_value_token.token_type {
-            TSXTokenType::Ident => {
-                // < or | or & or ; or } or , or [ or ident(next key) or .
-                let next = self.lexer.next_token();
+    }
+    // enum を取得したタイミングで利用する
+    // 値は sample に使わないので MEMBER の名前だけを読む
+    fn after_enum(&mut self) -> Result<(), ParseError> {
+        let name = self.expect(TSXTokenType::Ident)?;
+        self.expect(TSXTokenType::LCurlyBracket)?;
+        let mut members = Vec::new();
+        loop {
+            let member = self.next_token();
+            match member.token_type {
+                TSXTokenType::RCurlyBracket => break,
+                _ if Self::is_property_key(&member) => members.push(member.literal),
+                _ => return Err(ParseError::unexpected(&member)),
+            }
+            // = VALUE を , か } まで読み飛ばす
+            let mut depth = 0;
+            let mut next = self.next_token();
+            while depth > 0
+                || !matches!(
+                    next.token_type,
+                    TSXTokenType::Comma | TSXTokenType::RCurlyBracket
+                )
+            {
                 match next.token_type {
-                    TSXTokenType::Ident | TSXTokenType::RCurlyBracket => {
-                        self.peek = Some(next);
-                        match self.peek.as_ref().map(|t| t.token_type.clone()) {
-                            Some(TSXTokenType::Semicolon) => Type::Alias(type_value_token.literal),
-                            Some(TSXTokenType::Comma) => Type::Alias(type_value_token.literal),
-                            // }
-                            Some(TSXTokenType::RCurlyBracket) => {
-                                Type::Alias(type_value_token.literal)
-                            }
-                            Some(TSXTokenType::LBracket) => {
-                                let type_value = Type::Alias(type_value_token.literal);
-                                case_lbracket(self, type_value)
-                            }
-                            _ => {
-                                todo!("impl some type")
-                            }
-                        }
-                    }
-                    TSXTokenType::Semicolon | TSXTokenType::Comma => {
-                        Type::Alias(type_value_token.literal)
+                    TSXTokenType::LParentheses => depth += 1,
+                    TSXTokenType::RParentheses => depth -= 1,
+                    TSXTokenType::Eof => return Err(ParseError::unexpected(&next)),
+                    _ => {}
+                }
+                next = self.next_token();
+            }
+            if next.token_type == TSXTokenType::RCurlyBracket {
+                break;
+            }
+        }
+        self.symbols
+            .insert_enum(name.literal.clone(), EnumType::new(name.literal, members));
+        Ok(())
+    }
+    fn after_import(&mut self) -> Result<(), ParseError> {
+        let mut specifiers = Vec::new();
+        let mut next = self.lex_token();
+        // import("MODULE") や import.meta は宣言ではない
+        if next.token_type == TSXTokenType::LParentheses || next.token_type == TSXTokenType::Dot {
+            self.peek = Some(next);
+            return Ok(());
+        }
+        loop {
+            match next.token_type {
+                TSXTokenType::LCurlyBracket => {
+                    specifiers = self.read_specifiers()?;
+                }
+                TSXTokenType::From => {
+                    let module = self.expect(TSXTokenType::StringLiteral)?;
+                    for (name, local) in specifiers {
+                        self.symbols
+                            .insert_import(local, Import::new(name, &module.literal));
                     }
-                    TSXTokenType::Dot => {
-                        let after_type_value = self.get_type_value(ltag_num);
-                        Type::Alias(format!(
-                            "{}.{}",
-                            type_value_token.literal,
-                            after_type_value.to_str()
-                        ))
+                    return Ok(());
+                }
+                // import "./style.css"
+                TSXTokenType::StringLiteral => return Ok(()),
+                TSXTokenType::Semicolon | TSXTokenType::Eof => {
+                    return Err(ParseError::unexpected(&next))
+                }
+                // DEFAULT , type * as
+                _ => {}
+            }
+            next = self.lex_token();
+        }
+    }
+    // export { NAME } の後の from "MODULE" を読む
+    fn read_from_module(&mut self) -> Result<Option<String>, ParseError> {
+        let mut next = self.lex_token();
+        // export * as NAME from "MODULE"
+        while next.token_type == TSXTokenType::Ident {
+            next = self.lex_token();
+        }
+        if next.token_type != TSXTokenType::From {
+            self.peek = Some(next);
+            return Ok(None);
+        }
+        let module = self.expect(TSXTokenType::StringLiteral)?;
+        Ok(Some(module.literal))
+    }
+    // { を取得したタイミングで利用する
+    // { NAME, NAME as LOCAL, type NAME } の (NAME, LOCAL) を返す
+    fn read_specifiers(&mut self) -> Result<Vec<(String, String)>, ParseError> {
+        let mut specifiers = Vec::new();
+        let mut words: Vec<String> = Vec::new();
+        loop {
+            let token = self.lex_token();
+            match token.token_type {
+                TSXTokenType::Comma | TSXTokenType::RCurlyBracket => {
+                    if words.len() > 1 && words[0] == "type" {
+                        words.remove(0);
                     }
-                    TSXTokenType::RTag => {
-                        type_value_token.literal.push_str(">");
-                        let next = self.lexer.next_token();
-                        match next.token_type {
-                            TSXTokenType::Semicolon | TSXTokenType::Comma => {}
-                            TSXTokenType::Ident | TSXTokenType::RCurlyBracket => {
-                                self.peek = Some(next);
-                            }
-                            TSXTokenType::RTag => {
-                                return if ltag_num == 2 {
-                                    type_value_token.literal.push_str(">");
-                                    let next = self.lexer.next_token();
-                                    match next.token_type {
-                                        TSXTokenType::Semicolon | TSXTokenType::Comma => {}
-                                        TSXTokenType::Ident | TSXTokenType::RCurlyBracket => {
-                                            self.peek = Some(next);
-                                        }
-                                        TSXTokenType::Pipe => {
-                                            type_value_token.literal.push_str("|");
-                                            add_after_type_literal(self, &mut type_value_token);
-                                        }
-                                        _ => {
-                                            panic!("unexpected token {:?}", next)
-                                        }
-                                    }
-                                    Type::Alias(type_value_token.literal)
-                                } else {
-                                    self.get_type_value(ltag_num - 2)
-                                };
-                            }
-                            _ => {
-                                panic!("unexpected token {:?}", next)
-                            }
-                        };
-                        if ltag_num == 1 {
-                            Type::Alias(type_value_token.literal)
-                        } else {
-                            self.get_type_value(ltag_num - 1)
+                    match words.as_slice() {
+                        [] => {}
+                        [name] => specifiers.push((name.clone(), name.clone())),
+                        [name, r#as, local] if r#as == "as" => {
+                            specifiers.push((name.clone(), local.clone()))
                         }
+                        _ => return Err(ParseError::unexpected(&token)),
+                    }
+                    words.clear();
+                    if token.token_type == TSXTokenType::RCurlyBracket {
+                        return Ok(specifiers);
                     }
-                    TSXTokenType::LTag => {
-                        type_value_token.literal.push_str("<");
-                        let after_type_value_token = self.get_type_value(ltag_num + 1);
-                        type_value_token
-                            .literal
-                            .push_str(&after_type_value_token.to_str());
-                        Type::Alias(type_value_token.literal)
+                }
+                TSXTokenType::Eof => return Err(ParseError::unexpected(&token)),
+                _ => words.push(token.literal),
+            }
+        }
+    }
+    // KEY, "KEY", 0 or a keyword such as type and default
+    fn is_property_key(token: &TSXToken) -> bool {
+        match token.token_type {
+            TSXTokenType::Ident | TSXTokenType::StringLiteral | TSXTokenType::NumberLiteral => true,
+            TSXTokenType::Eof => false,
+            _ => {
+                token.literal.starts_with(char::is_alphabetic)
+                    && token.literal.chars().all(char::is_alphanumeric)
+            }
+        }
+    }
+    // React component names start with an uppercase letter
+    fn is_component_name(token: &TSXToken) -> bool {
+        token.token_type == TSXTokenType::Ident
+            && token
+                .literal
+                .chars()
+                .next()
+                .map(|c| c.is_uppercase())
+                .unwrap_or(false)
+    }
+    fn expect(&mut self, token_type: TSXTokenType) -> Result<TSXToken, ParseError> {
+        let token = self.next_token();
+        if token.token_type == token_type {
+            Ok(token)
+        } else {
+            Err(ParseError::unexpected(&token))
+        }
+    }
+    // source text of the token, "a" of a string literal with the quotes
+    fn raw(&self, token: &TSXToken) -> &str {
+        &self.source[token.span.start.offset..token.span.end.offset]
+    }
+    fn next_token(&mut self) -> TSXToken {
+        self.peek.take().unwrap_or_else(|| self.lex_token())
+    }
+    // comments are not part of any declaration, /** doc */ above a member included
+    fn lex_token(&mut self) -> TSXToken {
+        loop {
+            let token = self.lexer.next_token();
+            if token.token_type != TSXTokenType::Comment {
+                return token;
+            }
+        }
+    }
+    fn peek_token_type(&mut self) -> TSXTokenType {
+        if self.peek.is_none() {
+            self.peek = Some(self.lex_token());
+        }
+        self.peek.as_ref().unwrap().token_type.clone()
+    }
+    // :を取得したタイミングで利用する
+    // 型の後ろのトークン ( ; , } など ) は peek に残す
+    // `size-${number}` の ${} の中もそれぞれ型として読む
+    fn read_template_type(&mut self, token: &TSXToken) -> Result<Type, ParseError> {
+        let (quasis, starts) = Lexer::split_template(self.source, token.span.start);
+        let lexer = std::mem::replace(&mut self.lexer, Lexer::new(""));
+        let peek = self.peek.take();
+        let placeholders = starts
+            .into_iter()
+            .map(|start| self.read_placeholder(start))
+            .collect::<Result<Vec<_>, _>>();
+        self.lexer = lexer;
+        self.peek = peek;
+        Ok(Type::Literal(LiteralType::Template {
+            raw: self.raw(token).to_string(),
+            quasis,
+            placeholders: placeholders?,
+        }))
+    }
+    // { TYPE } of ${TYPE}, start is the {
+    fn read_placeholder(&mut self, start: Position) -> Result<Type, ParseError> {
+        self.lexer = Lexer::new_at(self.source, start);
+        self.peek = None;
+        self.expect(TSXTokenType::LCurlyBracket)?;
+        let ty = self.read_type()?;
+        self.expect(TSXTokenType::RCurlyBracket)?;
+        Ok(ty)
+    }
+    fn read_type(&mut self) -> Result<Type, ParseError> {
+        let first = self.next_token();
+        self.read_type_from(first)
+    }
+    // TYPE := ['|'] INTERSECTION ('|' INTERSECTION)*
+    fn read_type_from(&mut self, first: TSXToken) -> Result<Type, ParseError> {
+        let first = if first.token_type == TSXTokenType::Pipe {
+            self.next_token()
+        } else {
+            first
+        };
+        let mut members = vec![self.read_intersection_from(first)?];
+        while self.peek_token_type() == TSXTokenType::Pipe {
+            self.next_token();
+            let next = self.next_token();
+            members.push(self.read_intersection_from(next)?);
+        }
+        Ok(if members.len() == 1 {
+            members.remove(0)
+        } else {
+            Type::Union(members)
+        })
+    }
+    // INTERSECTION := ['&'] ARRAY ('&' ARRAY)*
+    fn read_intersection_from(&mut self, first: TSXToken) -> Result<Type, ParseError> {
+        let first = if first.token_type == TSXTokenType::And {
+            self.next_token()
+        } else {
+            first
+        };
+        let mut members = vec![self.read_array_from(first)?];
+        while self.peek_token_type() == TSXTokenType::And {
+            self.next_token();
+            let next = self.next_token();
+            members.push(self.read_array_from(next)?);
+        }
+        Ok(if members.len() == 1 {
+            members.remove(0)
+        } else {
+            Type::Intersection(members)
+        })
+    }
+    // ARRAY := PRIMARY ('[' ']' | '[' TYPE ']')*
+    fn read_array_from(&mut self, first: TSXToken) -> Result<Type, ParseError> {
+        let mut type_value = self.read_primary_type(first)?;
+        while self.peek_token_type() == TSXTokenType::LBracket {
+            self.next_token();
+            if self.peek_token_type() == TSXTokenType::RBracket {
+                self.next_token();
+                type_value = Type::Array(Box::new(type_value));
+                continue;
+            }
+            // Props["size"]
+            let index = self.read_type()?;
+            self.expect(TSXTokenType::RBracket)?;
+            type_value = Type::Indexed {
+                object: Box::new(type_value),
+                index: Box::new(index),
+            };
+        }
+        Ok(type_value)
+    }
+    fn read_primary_type(&mut self, token: TSXToken) -> Result<Type, ParseError> {
+        match token.token_type {
+            // keyof Props, keyof Props[] is keyof (Props[])
+            TSXTokenType::Ident if token.literal == "keyof" => {
+                let next = self.next_token();
+                Ok(Type::Keyof(Box::new(self.read_array_from(next)?)))
+            }
+            // typeof value.key, typeof value[number] is (typeof value)[number]
+            TSXTokenType::Ident if token.literal == "typeof" => {
+                let mut name = self.expect(TSXTokenType::Ident)?.literal;
+                while self.peek_token_type() == TSXTokenType::Dot {
+                    self.next_token();
+                    name.push('.');
+                    name.push_str(&self.expect(TSXTokenType::Ident)?.literal);
+                }
+                Ok(Type::Typeof(name))
+            }
+            // readonly string[]
+            TSXTokenType::Ident if token.literal == "readonly" => {
+                let next = self.next_token();
+                self.read_array_from(next)
+            }
+            TSXTokenType::Ident if token.literal == "string" => {
+                Ok(Type::Primitive(PrimitiveType::String))
+            }
+            TSXTokenType::Ident if token.literal == "number" => {
+                Ok(Type::Primitive(PrimitiveType::Number))
+            }
+            TSXTokenType::Ident if token.literal == "boolean" => {
+                Ok(Type::Primitive(PrimitiveType::Boolean))
+            }
+            TSXTokenType::Null => Ok(Type::Primitive(PrimitiveType::Null)),
+            TSXTokenType::Undefined => Ok(Type::Primitive(PrimitiveType::Undefined)),
+            TSXTokenType::Void => Ok(Type::Primitive(PrimitiveType::Void)),
+            // contextual keywords, still usable as names
+            TSXTokenType::Ident if token.literal == "any" => {
+                Ok(Type::Primitive(PrimitiveType::Any))
+            }
+            TSXTokenType::Ident if token.literal == "unknown" => {
+                Ok(Type::Primitive(PrimitiveType::Unknown))
+            }
+            TSXTokenType::Ident if token.literal == "never" => {
+                Ok(Type::Primitive(PrimitiveType::Never))
+            }
+            TSXTokenType::Ident if token.literal == "object" => {
+                Ok(Type::Primitive(PrimitiveType::Object))
+            }
+            TSXTokenType::Ident if token.literal == "bigint" => {
+                Ok(Type::Primitive(PrimitiveType::BigInt))
+            }
+            TSXTokenType::Ident if token.literal == "symbol" => {
+                Ok(Type::Primitive(PrimitiveType::Symbol))
+            }
+            // NAME, NAME.NAME, NAME<TYPE, TYPE>
+            TSXTokenType::Ident => {
+                let mut name = token.literal;
+                while self.peek_token_type() == TSXTokenType::Dot {
+                    self.next_token();
+                    name.push('.');
+                    name.push_str(&self.expect(TSXTokenType::Ident)?.literal);
+                }
+                if self.peek_token_type() == TSXTokenType::LTag {
+                    self.next_token();
+                    let args = self.read_type_arguments()?;
+                    return Ok(Type::Generic { name, args });
+                }
+                Ok(Type::Alias(name))
+            }
+            // "a" or 'a', the quotes are kept as written
+            TSXTokenType::StringLiteral => Ok(Type::Literal(LiteralType::String(
+                self.raw(&token).to_string(),
+            ))),
+            // `size-${number}`
+            TSXTokenType::TemplateLiteral => self.read_template_type(&token),
+            TSXTokenType::NumberLiteral => Ok(Type::Literal(LiteralType::Number(token.literal))),
+            TSXTokenType::True => Ok(Type::Literal(LiteralType::Boolean(true))),
+            TSXTokenType::False => Ok(Type::Literal(LiteralType::Boolean(false))),
+            TSXTokenType::Sub => {
+                let number = self.expect(TSXTokenType::NumberLiteral)?;
+                Ok(Type::Literal(LiteralType::Number(format!(
+                    "-{}",
+                    number.literal
+                ))))
+            }
+            TSXTokenType::LCurlyBracket => Ok(Type::Object(self.read_object_members()?)),
+            TSXTokenType::LBracket => Ok(Type::Tuple(self.read_tuple_elements()?)),
+            TSXTokenType::LParentheses => self.after_type_lparen(),
+            _ => Err(ParseError::unexpected(&token)),
+        }
+    }
+    // < を取得したタイミングで利用する
+    // 対応する > まで読み進める
+    fn read_type_arguments(&mut self) -> Result<Vec<Type>, ParseError> {
+        let mut arguments = Vec::new();
+        loop {
+            arguments.push(self.read_type()?);
+            let comma_or_rtag = self.next_token();
+            match comma_or_rtag.token_type {
+                TSXTokenType::Comma => {}
+                TSXTokenType::RTag => return Ok(arguments),
+                _ => return Err(ParseError::unexpected(&comma_or_rtag)),
+            }
+        }
+    }
+    // 型の中で [ を取得したタイミングで利用する
+    // [TYPE, LABEL?: TYPE, ...TYPE[]] を ] まで読む
+    fn read_tuple_elements(&mut self) -> Result<Vec<TupleElement>, ParseError> {
+        let mut elements = Vec::new();
+        loop {
+            let mut first = self.next_token();
+            // [] or [string, number,]
+            if first.token_type == TSXTokenType::RBracket {
+                return Ok(elements);
+            }
+            let rest = first.token_type == TSXTokenType::Dot;
+            if rest {
+                self.expect(TSXTokenType::Dot)?;
+                self.expect(TSXTokenType::Dot)?;
+                first = self.next_token();
+            }
+            let mut element = match (first.token_type.clone(), self.peek_token_type()) {
+                // [x: number]
+                (TSXTokenType::Ident, TSXTokenType::Colon) => {
+                    self.next_token();
+                    TupleElement::new(self.read_type()?).with_label(first.literal)
+                }
+                // [x?: number] or [number?]
+                (TSXTokenType::Ident, TSXTokenType::Question) => {
+                    self.next_token();
+                    if self.peek_token_type() == TSXTokenType::Colon {
+                        self.next_token();
+                        TupleElement::new(self.read_type()?)
+                            .with_label(first.literal)
+                            .optional()
+                    } else {
+                        TupleElement::new(self.read_array_from(first)?).optional()
                     }
-                    TSXTokenType::Pipe => {
-                        type_value_token.literal.push_str("|");
-                        add_after_type_literal(self, &mut type_value_token);
-                        Type::Alias(type_value_token.literal)
+                }
+                _ => {
+                    let element = TupleElement::new(self.read_type_from(first)?);
+                    if self.peek_token_type() == TSXTokenType::Question {
+                        self.next_token();
+                        element.optional()
+                    } else {
+                        element
                     }
-                    TSXTokenType::And => {
-                        type_value_token.literal.push_str("&");
-                        add_after_type_literal(self, &mut type_value_token);
-                        Type::Alias(type_value_token.literal)
+                }
+            };
+            if rest {
+                element = element.rest();
+            }
+            elements.push(element);
+            let comma_or_rbracket = self.next_token();
+            match comma_or_rbracket.token_type {
+                TSXTokenType::Comma => {}
+                TSXTokenType::RBracket => return Ok(elements),
+                _ => return Err(ParseError::unexpected(&comma_or_rbracket)),
+            }
+        }
+    }
+    // 型の中で ( を取得したタイミングで利用する
+    // (props:Props) => Type, () => Type or ("a" | "b")
+    fn after_type_lparen(&mut self) -> Result<Type, ParseError> {
+        let first = self.next_token();
+        match first.token_type {
+            TSXTokenType::RParentheses => self.after_function_params(Vec::new()),
+            // ...args
+            TSXTokenType::Dot => self.read_function_params(first),
+            TSXTokenType::Ident => {
+                let second = self.next_token();
+                match second.token_type {
+                    TSXTokenType::Colon | TSXTokenType::Question | TSXTokenType::Comma => {
+                        self.peek = Some(second);
+                        self.read_function_params(first)
                     }
-                    TSXTokenType::LBracket => {
-                        let mut array_num = 1;
-                        let rbracket = self.lexer.next_token();
-                        assert_eq!(rbracket.token_type, TSXTokenType::RBracket);
-                        self.peek = Some(self.lexer.next_token());
-
-                        while let Some(TSXTokenType::LBracket) =
-                            self.peek.as_ref().map(|t| t.token_type.clone())
-                        {
-                            let rbracket = self.lexer.next_token();
-                            assert_eq!(rbracket.token_type, TSXTokenType::RBracket);
-                            self.peek = Some(self.lexer.next_token());
-                            array_num += 1;
-                        }
-                        let mut type_value = Type::Alias(type_value_token.literal);
-                        for _ in 0..array_num {
-                            type_value = Type::Array(Box::new(type_value));
-                        }
-                        match self.peek.as_ref().map(|t| t.token_type.clone()) {
-                            Some(TSXTokenType::Semicolon) => {
-                                self.peek = Some(self.lexer.next_token());
-                            }
-                            Some(TSXTokenType::Comma) => {
-                                self.peek = Some(self.lexer.next_token());
-                            }
-                            _ => {}
+                    TSXTokenType::RParentheses => {
+                        if self.peek_token_type() == TSXTokenType::Arrow {
+                            self.after_function_params(vec![Param::new(first.literal, None)])
+                        } else {
+                            Ok(Type::Alias(first.literal))
                         }
-                        println!("array_num: {}", array_num);
-                        println!("type_value: {:?}", type_value);
-                        println!("peek: {:?}", self.peek);
-                        type_value
                     }
                     _ => {
-                        panic!("unexpected token {:?}", next)
+                        self.peek = Some(second);
+                        let type_value = self.read_type_from(first)?;
+                        self.expect(TSXTokenType::RParentheses)?;
+                        Ok(type_value)
                     }
                 }
             }
-            // case func
-            // (props:Props)=>Type;
-            // ()=>Type;
-            TSXTokenType::LParentheses => {
-                let mut next = self.lexer.next_token();
-                let mut type_value = String::from("(");
-                let mut count = 1;
-                while next.token_type != TSXTokenType::RParentheses && count != 0 {
-                    type_value.push_str(&next.literal);
-                    next = self.lexer.next_token();
-                    match next.token_type {
-                        TSXTokenType::LParentheses => {
-                            count += 1;
-                        }
-                        TSXTokenType::RParentheses => {
-                            count -= 1;
-                        }
-                        _ => {}
-                    }
-                }
-                type_value.push_str(")");
-                let arrow = self.lexer.next_token();
-                assert_eq!(arrow.token_type, TSXTokenType::Arrow);
-                type_value.push_str(" => ");
-                let return_type_value = self.get_type_value(0);
-                type_value.push_str(&return_type_value.to_str());
-                Type::Alias(type_value)
-            }
-            // case object
-            TSXTokenType::LCurlyBracket => {
-                let mut obj = ObjectType::new();
-                let mut next = self.lexer.next_token();
-                // TODO nested object
-                while next.token_type != TSXTokenType::RCurlyBracket {
-                    let key = next;
-                    let colon = self.lexer.next_token();
-                    assert_eq!(colon.token_type, TSXTokenType::Colon);
-                    let type_value = self.get_type_value(0);
-                    obj.insert(Key(key.literal), type_value);
-                    next = self.lexer.next_token();
-                }
-                self.peek = Some(self.lexer.next_token());
-                if Some(TSXTokenType::LBracket) == self.peek.as_ref().map(|t| t.token_type.clone())
-                {
-                    return case_lbracket(self, Type::Object(obj));
-                }
-                Type::Object(obj)
-            }
-            _ => panic!("unexpected token {:?}", type_value_token),
-        }
-    }
-    fn after_type_lcurl(&mut self, type_name: &str) {
+            _ => {
+                let type_value = self.read_type_from(first)?;
+                self.expect(TSXTokenType::RParentheses)?;
+                Ok(type_value)
+            }
+        }
+    }
+    // 最初の引数の名前を取得したタイミングで利用する
+    // NAME?: TYPE, ...NAME: TYPE を ) まで読み, (PARAMS) => TYPE にする
+    fn read_function_params(&mut self, first: TSXToken) -> Result<Type, ParseError> {
+        let mut params = Vec::new();
+        let mut name = first;
+        loop {
+            let mut param = if name.token_type == TSXTokenType::Dot {
+                self.expect(TSXTokenType::Dot)?;
+                self.expect(TSXTokenType::Dot)?;
+                Param::new(self.expect(TSXTokenType::Ident)?.literal, None).rest()
+            } else if Self::is_property_key(&name) {
+                Param::new(name.literal, None)
+            } else {
+                return Err(ParseError::unexpected(&name));
+            };
+            let mut next = self.next_token();
+            if next.token_type == TSXTokenType::Question {
+                param = param.optional();
+                next = self.next_token();
+            }
+            if next.token_type == TSXTokenType::Colon {
+                param.ty = Some(self.read_type()?);
+                next = self.next_token();
+            }
+            params.push(param);
+            match next.token_type {
+                TSXTokenType::RParentheses => break,
+                TSXTokenType::Comma => {}
+                _ => return Err(ParseError::unexpected(&next)),
+            }
+            name = self.next_token();
+            // (a: string,) => void
+            if name.token_type == TSXTokenType::RParentheses {
+                break;
+            }
+        }
+        self.after_function_params(params)
+    }
+    fn after_function_params(&mut self, params: Vec<Param>) -> Result<Type, ParseError> {
+        self.expect(TSXTokenType::Arrow)?;
+        let return_type = self.read_type()?;
+        Ok(Type::Function {
+            params,
+            ret: Box::new(return_type),
+        })
+    }
+    // interface NAME { KEY:TYPE }
+    // interface NAME extends Base { KEY:TYPE }
+    // interface NAME extends Base, React.HTMLAttributes<HTMLElement> { KEY:TYPE }
+    // interface NAME<T> extends Base<T> { KEY:TYPE }
+    fn after_interface_name(&mut self, type_name_token: &TSXToken) -> Result<(), ParseError> {
+        let type_name = type_name_token.literal.as_str();
+        // interface NAME<T> { KEY:T }
+        if self.peek_token_type() == TSXTokenType::LTag {
+            self.read_type_parameters()?;
+        }
+        let extends_or_lcurl = self.next_token();
+        let heritages = match extends_or_lcurl.token_type {
+            TSXTokenType::Extends => self.read_heritage_types()?,
+            TSXTokenType::LCurlyBracket => Vec::new(),
+            _ => return Err(ParseError::unexpected(&extends_or_lcurl)),
+        };
+        let type_value = self.read_object_members()?;
+        let props = if heritages.is_empty() {
+            NamedProps::new(type_name, type_value)
+        } else {
+            let mut types = vec![Type::Object(type_value)];
+            types.extend(heritages);
+            NamedProps::new_intersection_type(type_name, types)
+        };
+        self.symbols.insert_type(
+            type_name,
+            Props::Named(props.with_span(type_name_token.span)),
+        );
+        Ok(())
+    }
+    // extendsを取得したタイミングで利用する
+    // interfaceの本体の { まで読み進める
+    fn read_heritage_types(&mut self) -> Result<Vec<Type>, ParseError> {
+        let mut heritages = Vec::new();
+        loop {
+            heritages.push(self.read_type()?);
+            let comma_or_lcurl = self.next_token();
+            match comma_or_lcurl.token_type {
+                TSXTokenType::Comma => {}
+                TSXTokenType::LCurlyBracket => return Ok(heritages),
+                _ => return Err(ParseError::unexpected(&comma_or_lcurl)),
+            }
+        }
+    }
+    // { を取得したタイミングで利用する
+    // 対応する } まで読み進める
+    fn read_object_members(&mut self) -> Result<ObjectType, ParseError> {
         let mut type_value = ObjectType::new();
-        let key_or_rcurl = self.lexer.next_token();
-        if key_or_rcurl.token_type == TSXTokenType::RCurlyBracket {
-            self.type_buffer.insert(
-                type_name.to_string(),
-                Props::Named(NamedProps::new(type_name, type_value)),
-            );
-            return;
-        }
-        let mut key = key_or_rcurl;
         loop {
-            let colon_or_question = self.lexer.next_token();
-            if colon_or_question.token_type == TSXTokenType::Question {
-                let colon = self.lexer.next_token();
-                key = TSXToken::new(TSXTokenType::Ident, format!("{}?", key.literal));
-                assert_eq!(colon.token_type, TSXTokenType::Colon);
-            }
-            let type_literal = self.get_type_value(0);
-            type_value.insert(Key(key.literal.clone()), type_literal);
-
-            let key_or_rcurl = self.peek.take().unwrap_or_else(|| self.lexer.next_token());
-            match key_or_rcurl.token_type {
-                TSXTokenType::Ident => {
-                    key = key_or_rcurl;
-                    continue;
-                }
-                TSXTokenType::RCurlyBracket => {
-                    self.peek = Some(self.lexer.next_token());
-                    while Some(TSXTokenType::Semicolon)
-                        == self.peek.as_ref().map(|t| t.token_type.clone())
-                    {
-                        self.peek = Some(self.lexer.next_token());
-                    }
-                    match self.peek.as_ref().map(|t| t.token_type.clone()) {
-                        Some(TSXTokenType::Pipe) => {
-                            self.lexer.next_token();
-                        }
-                        Some(TSXTokenType::And) => {
-                            let indent = self.lexer.next_token();
-                            assert_eq!(indent.token_type, TSXTokenType::Ident);
-                            // TODO:全然できてない.一つだけの&であればOK
-                            // それ以外はpanic
-                            self.type_buffer.insert(
-                                type_name.to_string(),
-                                Props::Named(NamedProps::new_intersection_type(
-                                    type_name,
-                                    vec![
-                                        Type::Object(type_value),
-                                        Type::Alias(indent.literal.clone()),
-                                    ],
-                                )),
-                            );
-                            break;
-                        }
-                        _ => {}
-                    }
-                    self.type_buffer.insert(
-                        type_name.to_string(),
-                        Props::Named(NamedProps::new(type_name, type_value)),
-                    );
-                    break;
-                }
-                // not implement yet
-                TSXTokenType::LTag
-                | TSXTokenType::RTag
-                | TSXTokenType::Or
-                | TSXTokenType::Pipe
-                | TSXTokenType::And => {}
-                _ => {
-                    panic!("unexpected token {:?}", key_or_rcurl)
+            let key = self.next_token();
+            match key.token_type {
+                TSXTokenType::RCurlyBracket => return Ok(type_value),
+                TSXTokenType::Semicolon | TSXTokenType::Comma => continue,
+                // type?: "button" | "submit" のようにkeyは予約語でもよい
+                _ if Self::is_property_key(&key) => {}
+                _ => return Err(ParseError::unexpected(&key)),
+            }
+            let mut key = key;
+            let mut colon_or_question = self.next_token();
+            // readonly KEY: TYPE, readonly 自体がkeyの場合は後ろが : か ?
+            let readonly = key.literal == "readonly" && Self::is_property_key(&colon_or_question);
+            if readonly {
+                key = colon_or_question;
+                colon_or_question = self.next_token();
+            }
+            let optional = match colon_or_question.token_type {
+                TSXTokenType::Colon => false,
+                TSXTokenType::Question => {
+                    self.expect(TSXTokenType::Colon)?;
+                    true
                 }
+                _ => return Err(ParseError::unexpected(&colon_or_question)),
+            };
+            let mut property = Property::new(self.read_type()?);
+            if optional {
+                property = property.optional();
             }
+            if readonly {
+                property = property.readonly();
+            }
+            type_value.insert_property(Key(key.literal), property);
         }
     }
     // export const NAME:React.FC<Type> = (props:Props) => {}
     // export const NAME:FC<Type> = (props:Props) => {}
     // export const NAME:VFC<Type> = (props:Props) => {}
+    // export const NAME:FC<{key:value....}> = (props) => {}
+    // export const NAME:React.FC = () => {}
     // export const NAME = (props:Props) => {}
     // export const NAME = (props:{key:value....}) => {}
-    fn after_const_name(&mut self, component_name: &str) -> Option<Component> {
-        let colon_or_eq = self.lexer.next_token();
+    fn after_const_name(&mut self, component_name: &str) -> Result<Option<Component>, ParseError> {
+        let colon_or_eq = self.lex_token();
         match colon_or_eq.token_type {
             TSXTokenType::Colon => {
-                fn case_fc_or_vfc(
-                    this: &mut ComponentPartsParser,
-                    focus_token: &TSXToken,
-                    component_name: &str,
-                ) -> Option<Component> {
-                    if focus_token.literal == "FC" || focus_token.literal == "VFC" {
-                        let _lt = this.lexer.next_token();
-                        let type_name = this.lexer.next_token();
-                        let props = this.type_buffer.remove(&type_name.literal);
-                        if let Some(props) = props {
-                            return Some(Component::new(component_name, props));
-                        }
-                        return Some(Component::new(
-                            component_name,
-                            Props::Named(NamedProps::new(type_name.literal, ObjectType::new())),
-                        ));
-                    };
-                    None
+                let mut type_name = self.next_token();
+                if type_name.literal == "React" && self.peek_token_type() == TSXTokenType::Dot {
+                    self.next_token();
+                    type_name = self.next_token();
                 }
-                let type_name = self.lexer.next_token();
-                if let Some(component) = case_fc_or_vfc(self, &type_name, component_name) {
-                    return Some(component);
+                if type_name.literal != "FC" && type_name.literal != "VFC" {
+                    return Ok(None);
                 }
-                if type_name.literal == "React" {
-                    let _dot = self.lexer.next_token();
-                    let _fc_or_vfc = self.lexer.next_token();
-                    if let Some(component) = case_fc_or_vfc(self, &_fc_or_vfc, component_name) {
-                        return Some(component);
-                    }
+                // React.FC のように props の型が無い
+                if self.peek_token_type() != TSXTokenType::LTag {
+                    return Ok(Some(Component::new(
+                        component_name,
+                        Props::Expand(ObjectType::new()),
+                    )));
                 }
-                None
+                self.next_token();
+                let props = self.read_props_type(&[])?;
+                Ok(Some(Component::new(component_name, props)))
             }
             TSXTokenType::Assign => {
-                let lp = self.lexer.next_token();
-                assert_eq!(lp.token_type, TSXTokenType::LParentheses);
-                self.from_props_lparen(component_name)
+                // export const NAME = <T,>(props:Props<T>) => {}
+                let type_params = if self.peek_token_type() == TSXTokenType::LTag {
+                    self.read_type_parameters()?
+                } else {
+                    Vec::new()
+                };
+                let lparen = self.next_token();
+                if lparen.token_type != TSXTokenType::LParentheses {
+                    // export const NAME = Value
+                    self.peek = Some(lparen);
+                    return Ok(None);
+                }
+                self.after_props_lparen(component_name, &type_params)
             }
-            _ => None,
+            _ => Ok(None),
         }
     }
-    fn from_props_lparen(&mut self, component_name: &str) -> Option<Component> {
-        let props_or_rpar = self.lexer.next_token();
-        // props なし
-        if props_or_rpar.token_type == TSXTokenType::RParentheses {
-            return Some(Component::new(
-                component_name,
-                Props::Expand(ObjectType::new()),
-            ));
-        }
-        let colon = self.lexer.next_token();
-        assert_eq!(colon.token_type, TSXTokenType::Colon);
-        let props_name_or_lcurl = self.lexer.next_token();
-        // case props is named
-        if props_name_or_lcurl.token_type == TSXTokenType::Ident {
-            let props_name = props_name_or_lcurl;
-            assert_eq!(props_name.token_type, TSXTokenType::Ident);
-            let props = self.type_buffer.remove(&props_name.literal);
-            if let Some(props) = props {
-                return Some(Component::new(component_name, props));
-            }
-            return Some(Component::new(
-                component_name,
-                Props::Named(NamedProps::new(props_name.literal, ObjectType::new())),
-            ));
-        }
-        // case props is expand
-        let key_or_rcurl = self.lexer.next_token();
-        // case {}
-        if key_or_rcurl.token_type == TSXTokenType::RCurlyBracket {
-            return Some(Component::new(
+    // (props:Props), (props:{key:value....}), ({ key }:Props) or (props)
+    // 型の分からない props は props なしとして扱い, component は残す
+    fn after_props_lparen(
+        &mut self,
+        component_name: &str,
+        type_params: &[String],
+    ) -> Result<Option<Component>, ParseError> {
+        let props_or_rpar = self.next_token();
+        match props_or_rpar.token_type {
+            // props なし
+            TSXTokenType::RParentheses => {
+                return Ok(Some(Component::new(
+                    component_name,
+                    Props::Expand(ObjectType::new()),
+                )))
+            }
+            // ({ label, size = "md" }: Props)
+            TSXTokenType::LCurlyBracket | TSXTokenType::LBracket => self.skip_block(),
+            _ => {}
+        }
+        // (props) => {} のように型が無い
+        if self.peek_token_type() != TSXTokenType::Colon {
+            return Ok(Some(Component::new(
                 component_name,
                 Props::Expand(ObjectType::new()),
-            ));
-        }
-
-        let mut key = key_or_rcurl;
-        let mut obj = ObjectType::new();
-        while key.token_type != TSXTokenType::RCurlyBracket {
-            let colon_or_question = self.lexer.next_token();
-            let obj_key = if colon_or_question.token_type == TSXTokenType::Question {
-                let colon = self.lexer.next_token();
-                assert_eq!(colon.token_type, TSXTokenType::Colon);
-                Key(format!("{}?", key.literal))
-            } else {
-                Key(key.literal.clone())
-            };
-            let type_value = self.get_type_value(0);
-            obj.insert(obj_key, type_value);
-            key = self.lexer.next_token();
+            )));
         }
-
-        Some(Component::new(component_name, Props::Expand(obj)))
+        self.next_token();
+        let props = self.read_props_type(type_params)?;
+        Ok(Some(Component::new(component_name, props)))
+    }
+    // Props, Props<T> か {key:value....} を読む
+    // それ以外は props なしとして扱う, 読めない object はエラー
+    // type_params は component の <T> で, story には無いので unknown にする
+    fn read_props_type(&mut self, type_params: &[String]) -> Result<Props, ParseError> {
+        let props_name_or_lcurl = self.next_token();
+        Ok(match props_name_or_lcurl.token_type {
+            // case props is named
+            TSXTokenType::Ident => {
+                let mut args = Vec::new();
+                if self.peek_token_type() == TSXTokenType::LTag {
+                    self.next_token();
+                    args = self.read_type_arguments()?;
+                }
+                for arg in &mut args {
+                    let Ok(()) = arg.resolve_aliases(&mut |name| {
+                        Ok::<_, Infallible>(
+                            type_params
+                                .iter()
+                                .any(|param| param == name)
+                                .then_some(Type::Primitive(PrimitiveType::Unknown)),
+                        )
+                    });
+                }
+                Props::Named(
+                    NamedProps::new(props_name_or_lcurl.literal, ObjectType::new()).with_args(args),
+                )
+            }
+            // case props is expand
+            TSXTokenType::LCurlyBracket => Props::Expand(self.read_object_members()?),
+            _ => {
+                self.peek = Some(props_name_or_lcurl);
+                Props::Expand(ObjectType::new())
+            }
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::component::{Key, NamedProps, ObjectType, Props, Type};
+    use crate::{
+        cli::ActionStyle,
+        component::{
+            Key, LiteralType, NamedProps, ObjectType, PrimitiveType, Property, Props,
+            SampleOptions, Type,
+        },
+        token::Span,
+    };
+
+    use super::*;
     #[test]
     fn test_to_obj_array() {
         let content = r#"
@@ -536,21 +1166,21 @@ export const WordDetail = (props: WordDetailProps) => {
   );
 };
 "#;
-        let content = TSXContent(content.to_string());
-        let component = content.to_component();
+        let content = TSXContent::new(content);
+        let components = content.to_components();
         let mut props = ObjectType::new();
         props.insert(
             Key("wordMeaning".to_string()),
-            Type::Alias("string".to_string()),
+            Type::Primitive(PrimitiveType::String),
         );
         let mut sentences = ObjectType::new();
         sentences.insert(
             Key("sentence".to_string()),
-            Type::Alias("string".to_string()),
+            Type::Primitive(PrimitiveType::String),
         );
         sentences.insert(
             Key("meaning".to_string()),
-            Type::Alias("string".to_string()),
+            Type::Primitive(PrimitiveType::String),
         );
         props.insert(
             Key("sentences".to_string()),
@@ -558,13 +1188,16 @@ export const WordDetail = (props: WordDetailProps) => {
         );
         props.insert(
             Key("playAudio".to_string()),
-            Type::Alias("() => void".to_string()),
+            Type::Function {
+                params: Vec::new(),
+                ret: Box::new(Type::Primitive(PrimitiveType::Void)),
+            },
         );
         let expect = Component::new(
             "WordDetail",
             Props::Named(NamedProps::new_object_type("WordDetailProps", props)),
         );
-        assert_eq!(component.unwrap(), expect);
+        assert_eq!(components.unwrap(), vec![expect]);
     }
     #[test]
     fn test_to_array() {
@@ -595,25 +1228,27 @@ export const WordDetail = (props: WordDetailProps) => {
   );
 };
 "#;
-        let content = TSXContent(content.to_string());
-        let component = content.to_component();
+        let content = TSXContent::new(content);
+        let components = content.to_components();
         let mut props = ObjectType::new();
         props.insert(
             Key("wordMeanings".to_string()),
-            Type::Array(Box::new(Type::Array(Box::new(Type::Alias(
-                "string".to_string(),
+            Type::Array(Box::new(Type::Array(Box::new(Type::Primitive(
+                PrimitiveType::String,
             ))))),
         );
-        let mut sentences = ObjectType::new();
         props.insert(
             Key("playAudio".to_string()),
-            Type::Alias("() => void".to_string()),
+            Type::Function {
+                params: Vec::new(),
+                ret: Box::new(Type::Primitive(PrimitiveType::Void)),
+            },
         );
         let expect = Component::new(
             "WordDetail",
             Props::Named(NamedProps::new_object_type("WordDetailProps", props)),
         );
-        assert_eq!(component.unwrap(), expect);
+        assert_eq!(components.unwrap(), vec![expect]);
     }
     #[test]
     fn test_to_and_type() {
@@ -641,8 +1276,8 @@ const Container = styled.div`
   position: relative;
 `;
 "#;
-        let content = TSXContent(content.to_string());
-        let component = content.to_component();
+        let content = TSXContent::new(content);
+        let components = content.to_components();
         let mut props = ObjectType::new();
         props.insert(
             Key("button".to_string()),
@@ -658,43 +1293,751 @@ const Container = styled.div`
                 ],
             )),
         );
-        assert_eq!(component.unwrap(), expect);
+        assert_eq!(components.unwrap(), vec![expect]);
     }
 
-    use super::*;
     #[test]
-    fn test_to_union_generic() {
+    fn test_to_type_alias_expression() {
         let content = r#"
-import * as React from "react";
-import AddIcon from "@mui/icons-material/Add";
-import { Fab } from "@mui/material";
+type Base = { id: string };
+type Sized = { size: "sm" | "lg" };
+export type CardProps<T = unknown> = (Base & Sized) &
+  Omit<Extra, "hidden"> & {
+    title: string;
+  };
+type Extra = { hidden: boolean; note?: string };
+type ShapeProps =
+  | { kind: "circle"; radius: number }
+  | { kind: "square"; side: number };
 
-export const DeleteConfirmModal = (props: {
-  deleteHandler: () => Promise<void>;
-  setOpen: React.Dispatch<React.SetStateAction<boolean>> | union;
-  open: boolean;
-}) => {
-  return (
-    <Fab color="primary" aria-label="add" size="small">
+export const Card = (props: CardProps) => {
+  return <div />;
+};
+export const Shape = (props: ShapeProps) => {
+  return <svg />;
+};
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components().unwrap();
+        let options = SampleOptions::new(ActionStyle::Fn);
+        assert_eq!(
+            components[0].fill_sample(&options),
+            r#"{ id: "",note: "",size: "sm",title: "", }"#
+        );
+        assert_eq!(components[0].props_str(), "CardProps");
+        assert_eq!(
+            components[1].fill_sample(&options),
+            r#"{ kind: "circle",radius: 0, }"#
+        );
+    }
+    #[test]
+    fn test_to_intersection_with_unknown() {
+        let content = r#"
+import { InputFieldProps } from "some-ui";
+
+type FieldProps = InputFieldProps & { label: string } & { size: number };
+export const Field = (props: FieldProps) => {
+  return <input />;
+};
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components().unwrap();
+        let options = SampleOptions::new(ActionStyle::Fn);
+        // InputFieldProps は分からないので sample に含めない
+        assert_eq!(
+            components[0].fill_sample(&options),
+            r#"{ label: "",size: 0, }"#
+        );
+    }
+    #[test]
+    fn test_to_type_operators() {
+        let content = r#"
+const themes = { light: "white", dark: "black" };
+type Modes = { dark: boolean; light: boolean };
+type Sizes = { sm: 8; lg: 16 };
+type PanelProps = {
+  mode: keyof Modes;
+  size: Sizes["sm" | "lg"];
+  gap: Sizes["sm"];
+  theme: keyof typeof themes;
+};
+export const Panel = (props: PanelProps) => <div />;
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components().unwrap();
+        let args = components[0].fill_sample(&SampleOptions::new(ActionStyle::Fn));
+        // typeof は値を読まないので分からない
+        assert_eq!(args, r#"{ gap: 8,mode: "dark",size: 8,theme: undefined, }"#);
+        // literals and undefined only, so the args are a type literal that parses too
+        let content = format!(
+            "type Args = {};\nexport const Check = (props: Args) => <div />;",
+            args
+        );
+        assert!(TSXContent::new(&content).to_components().is_ok());
+    }
+    #[test]
+    fn test_to_union_generic() {
+        let content = r#"
+import * as React from "react";
+import AddIcon from "@mui/icons-material/Add";
+import { Fab } from "@mui/material";
+
+export const DeleteConfirmModal = (props: {
+  deleteHandler: () => Promise<void>;
+  setOpen: React.Dispatch<React.SetStateAction<boolean>> | union;
+  open: boolean;
+}) => {
+  return (
+    <Fab color="primary" aria-label="add" size="small">
       <AddIcon sx={{}} onClick={props.handler}></AddIcon>
     </Fab>
   );
 };
 "#;
-        let content = TSXContent(content.to_string());
-        let component = content.to_component();
+        let content = TSXContent::new(content);
+        let components = content.to_components();
         let mut props = ObjectType::new();
         props.insert(
             Key("deleteHandler".to_string()),
-            Type::Alias("() => Promise<void>".to_string()),
+            Type::Function {
+                params: Vec::new(),
+                ret: Box::new(Type::Generic {
+                    name: "Promise".to_string(),
+                    args: vec![Type::Primitive(PrimitiveType::Void)],
+                }),
+            },
+        );
+        props.insert(
+            Key("open".to_string()),
+            Type::Primitive(PrimitiveType::Boolean),
         );
-        props.insert(Key("open".to_string()), Type::Alias("boolean".to_string()));
         props.insert(
             Key("setOpen".to_string()),
-            Type::Alias("React.Dispatch<React.SetStateAction<boolean>>|union".to_string()),
+            Type::Union(vec![
+                Type::Generic {
+                    name: "React.Dispatch".to_string(),
+                    args: vec![Type::Generic {
+                        name: "React.SetStateAction".to_string(),
+                        args: vec![Type::Primitive(PrimitiveType::Boolean)],
+                    }],
+                },
+                Type::Alias("union".to_string()),
+            ]),
         );
         let expect = Component::new("DeleteConfirmModal", Props::Expand(props));
-        assert_eq!(component.unwrap(), expect);
+        assert_eq!(components.unwrap(), vec![expect]);
+    }
+    #[test]
+    fn test_to_keyword_types() {
+        let content = r#"
+export const Debug = (props: {
+  value: any;
+  raw: unknown;
+  error: string | null | undefined;
+  meta: object;
+  id: bigint;
+  key: symbol;
+  onClose: () => void;
+  never: never;
+}) => {
+  return <pre />;
+};
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components().unwrap();
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("value".to_string()),
+            Type::Primitive(PrimitiveType::Any),
+        );
+        props.insert(
+            Key("raw".to_string()),
+            Type::Primitive(PrimitiveType::Unknown),
+        );
+        props.insert(
+            Key("error".to_string()),
+            Type::Union(vec![
+                Type::Primitive(PrimitiveType::String),
+                Type::Primitive(PrimitiveType::Null),
+                Type::Primitive(PrimitiveType::Undefined),
+            ]),
+        );
+        props.insert(
+            Key("meta".to_string()),
+            Type::Primitive(PrimitiveType::Object),
+        );
+        props.insert(
+            Key("id".to_string()),
+            Type::Primitive(PrimitiveType::BigInt),
+        );
+        props.insert(
+            Key("key".to_string()),
+            Type::Primitive(PrimitiveType::Symbol),
+        );
+        props.insert(
+            Key("onClose".to_string()),
+            Type::Function {
+                params: Vec::new(),
+                ret: Box::new(Type::Primitive(PrimitiveType::Void)),
+            },
+        );
+        props.insert(
+            Key("never".to_string()),
+            Type::Primitive(PrimitiveType::Never),
+        );
+        let expect = Component::new("Debug", Props::Expand(props));
+        assert_eq!(components, vec![expect]);
+        assert_eq!(
+            components[0].fill_sample(&SampleOptions::new(ActionStyle::Fn)),
+            r#"{ error: "",id: 0n,key: Symbol(),meta: {},never: undefined,onClose: fn(),raw: undefined,value: undefined, }"#
+        );
+        assert_eq!(
+            components[0].variants(),
+            vec![
+                ("error".to_string(), "null".to_string()),
+                ("error".to_string(), "undefined".to_string()),
+            ]
+        );
+    }
+    #[test]
+    fn test_to_union_literal() {
+        let content = r#"
+type ButtonProps = {
+  variant: "primary" | "secondary" | "danger";
+  size?:
+    | 1
+    | -1;
+  icon: (Icon | null)[];
+  onClick: (event: MouseEvent<HTMLButtonElement>) => void;
+  checked: true | false;
+};
+export const Button = (props: ButtonProps) => {
+  return <button />;
+};
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components();
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("checked".to_string()),
+            Type::Union(vec![
+                Type::Literal(LiteralType::Boolean(true)),
+                Type::Literal(LiteralType::Boolean(false)),
+            ]),
+        );
+        props.insert(
+            Key("variant".to_string()),
+            Type::Union(vec![
+                Type::Literal(LiteralType::String("\"primary\"".to_string())),
+                Type::Literal(LiteralType::String("\"secondary\"".to_string())),
+                Type::Literal(LiteralType::String("\"danger\"".to_string())),
+            ]),
+        );
+        props.insert_property(
+            Key("size".to_string()),
+            Property::new(Type::Union(vec![
+                Type::Literal(LiteralType::Number("1".to_string())),
+                Type::Literal(LiteralType::Number("-1".to_string())),
+            ]))
+            .optional(),
+        );
+        props.insert(
+            Key("icon".to_string()),
+            Type::Array(Box::new(Type::Union(vec![
+                Type::Alias("Icon".to_string()),
+                Type::Primitive(PrimitiveType::Null),
+            ]))),
+        );
+        props.insert(
+            Key("onClick".to_string()),
+            Type::Function {
+                params: vec![Param::new(
+                    "event",
+                    Some(Type::Generic {
+                        name: "MouseEvent".to_string(),
+                        args: vec![Type::Alias("HTMLButtonElement".to_string())],
+                    }),
+                )],
+                ret: Box::new(Type::Primitive(PrimitiveType::Void)),
+            },
+        );
+        let expect = Component::new(
+            "Button",
+            Props::Named(NamedProps::new("ButtonProps", props)),
+        );
+        let components = components.unwrap();
+        assert_eq!(components, vec![expect]);
+        assert!(components[0]
+            .expand_str()
+            .contains(r#"variant: "primary" | "secondary" | "danger","#));
+    }
+    #[test]
+    fn test_to_quoted_literal() {
+        let content = r#"
+const label = 'it\'s {';
+type TagProps = {
+  tone: 'info' | "it's";
+  size: `size-${number}`;
+};
+export const Tag = (props: TagProps) => {
+  return <span className={`tag-${props.tone}`}>{'}'}</span>;
+};
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components().unwrap();
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("tone".to_string()),
+            Type::Union(vec![
+                Type::Literal(LiteralType::String("'info'".to_string())),
+                Type::Literal(LiteralType::String("\"it's\"".to_string())),
+            ]),
+        );
+        props.insert(
+            Key("size".to_string()),
+            Type::Literal(LiteralType::Template {
+                raw: "`size-${number}`".to_string(),
+                quasis: vec!["size-".to_string(), "".to_string()],
+                placeholders: vec![Type::Primitive(PrimitiveType::Number)],
+            }),
+        );
+        let expect = Component::new("Tag", Props::Named(NamedProps::new("TagProps", props)));
+        assert_eq!(components, vec![expect]);
+        let options = SampleOptions::new(ActionStyle::Fn);
+        assert_eq!(
+            components[0].fill_sample(&options),
+            r#"{ size: `size-0`,tone: 'info', }"#
+        );
+    }
+    #[test]
+    fn test_to_template_placeholders() {
+        let content = r#"
+type Size = "sm" | "lg";
+type ChipProps = {
+  separator: `a${"|" | "/"}b`;
+  nested: `x-${`${Size}-${number}`}px`;
+  size: `chip-${Size}`;
+};
+export const Chip = (props: ChipProps) => <span />;
+"#;
+        let components = TSXContent::new(content).to_components().unwrap();
+        let options = SampleOptions::new(ActionStyle::Fn);
+        assert_eq!(
+            components[0].fill_sample(&options),
+            r#"{ nested: `x-sm-0px`,separator: `a|b`,size: `chip-sm`, }"#
+        );
+
+        // ${} の中の読めない型はその位置で報告する
+        let content = r#"
+type ChipProps = {
+  size: `chip-${
+    <}`;
+};
+export const Chip = (props: ChipProps) => <span />;
+"#;
+        let err = TSXContent::new(content).to_components().unwrap_err();
+        assert_eq!(err.to_string(), "4:5: unexpected `<`");
+    }
+    #[test]
+    fn test_to_skip_bodies() {
+        let content = r#"
+const isValid = (value: string) => value.length % 2 < 1;
+class Store {
+  type = "store";
+  interface: Props = { label: "" };
+}
+export const Card = (props: CardProps) => {
+  const $items = useMemo(() => <T,>(items: T[]) => items, []);
+  return (
+    <section className={`card ${props.tone}`}>
+      <input type="text" value={props.label} />
+      Don't {props.items.map((item) => <li key={item}>{"}"}</li>)}
+    </section>
+  );
+};
+export const Badge = (props: BadgeProps) => <span>it's {props.label}</span>;
+type CardProps = { label: string };
+export type BadgeProps = { label: string };
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components().unwrap();
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("label".to_string()),
+            Type::Primitive(PrimitiveType::String),
+        );
+        assert_eq!(
+            components,
+            vec![
+                Component::new(
+                    "Card",
+                    Props::Named(NamedProps::new_object_type("CardProps", props.clone()))
+                ),
+                Component::new(
+                    "Badge",
+                    Props::Named(NamedProps::new_object_type("BadgeProps", props))
+                ),
+            ]
+        );
+    }
+    #[test]
+    fn test_to_skip_comments() {
+        let content = r#"
+export const Card = (props: CardProps) => {
+  /* keep the label (see Avatar */
+  // } is not the end of the body
+  return <section>{props.label}</section>;
+};
+/**
+ * a round image (falls back to initials
+ */
+export const Avatar = (props: CardProps) => <img alt={props.label} />;
+type CardProps = { label: string };
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components().unwrap();
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("label".to_string()),
+            Type::Primitive(PrimitiveType::String),
+        );
+        assert_eq!(
+            components,
+            vec![
+                Component::new(
+                    "Card",
+                    Props::Named(NamedProps::new_object_type("CardProps", props.clone()))
+                ),
+                Component::new(
+                    "Avatar",
+                    Props::Named(NamedProps::new_object_type("CardProps", props))
+                ),
+            ]
+        );
+    }
+    #[test]
+    fn test_to_regex_in_helper() {
+        let content = r#"
+const isQuoted = (value: string) => /^[`'"].*\/$/.test(value);
+export const Quote = (props: QuoteProps) => {
+  return <q>{props.text}</q>;
+};
+type QuoteProps = { text: string };
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components().unwrap();
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("text".to_string()),
+            Type::Primitive(PrimitiveType::String),
+        );
+        assert_eq!(
+            components,
+            vec![Component::new(
+                "Quote",
+                Props::Named(NamedProps::new_object_type("QuoteProps", props))
+            )]
+        );
+    }
+    #[test]
+    fn test_to_number_literal() {
+        let content = r#"
+type GridProps = {
+  columns: 1_000 | 0xff | 1.5e3 | .5 | -1e-3;
+  id: 10n;
+};
+export const Grid = (props: GridProps) => {
+  return <div />;
+};
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components().unwrap();
+        let number = |s: &str| Type::Literal(LiteralType::Number(s.to_string()));
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("columns".to_string()),
+            Type::Union(vec![
+                number("1_000"),
+                number("0xff"),
+                number("1.5e3"),
+                number(".5"),
+                number("-1e-3"),
+            ]),
+        );
+        props.insert(Key("id".to_string()), number("10n"));
+        let expect = Component::new("Grid", Props::Named(NamedProps::new("GridProps", props)));
+        assert_eq!(components, vec![expect]);
+        let options = SampleOptions::new(ActionStyle::Fn);
+        assert_eq!(
+            components[0].fill_sample(&options),
+            "{ columns: 1_000,id: 10n, }"
+        );
+    }
+    #[test]
+    fn test_to_function_params() {
+        let content = r#"
+export const Search = (props: {
+  onSearch: (query: string, page?: number, ...filters: string[]) => Promise<void>;
+  onReset: (event) => void;
+}) => {
+  return <input />;
+};
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components();
+        let on_search = Type::Function {
+            params: vec![
+                Param::new("query", Some(Type::Primitive(PrimitiveType::String))),
+                Param::new("page", Some(Type::Primitive(PrimitiveType::Number))).optional(),
+                Param::new(
+                    "filters",
+                    Some(Type::Array(Box::new(Type::Primitive(
+                        PrimitiveType::String,
+                    )))),
+                )
+                .rest(),
+            ],
+            ret: Box::new(Type::Generic {
+                name: "Promise".to_string(),
+                args: vec![Type::Primitive(PrimitiveType::Void)],
+            }),
+        };
+        assert_eq!(
+            on_search.to_str(),
+            "(query: string, page?: number, ...filters: string[]) => Promise<void>"
+        );
+        let mut props = ObjectType::new();
+        props.insert(Key("onSearch".to_string()), on_search);
+        props.insert(
+            Key("onReset".to_string()),
+            Type::Function {
+                params: vec![Param::new("event", None)],
+                ret: Box::new(Type::Primitive(PrimitiveType::Void)),
+            },
+        );
+        let expect = Component::new("Search", Props::Expand(props));
+        assert_eq!(components.unwrap(), vec![expect]);
+    }
+    #[test]
+    fn test_to_grouped_types() {
+        let content = r#"
+export const Toolbar = (props: {
+  icons: (Icon | null)[];
+  onClose: (() => void) | null;
+  sizes: ("sm" | "lg")[] & { length: 2 };
+}) => {
+  return <div />;
+};
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components().unwrap();
+        // () がないと Icon | null[] や () => void | null のように意味が変わる
+        assert_eq!(
+            components[0].props_str(),
+            r#"{ icons: (Icon | null)[],onClose: (() => void) | null,sizes: { length: 2, } & ("sm" | "lg")[], }"#
+        );
+    }
+    #[test]
+    fn test_to_tuple() {
+        let content = r#"
+export const Chart = (props: {
+  range: [number, number];
+  point: [x: number, y: number, label?: string];
+  path: readonly [string, ...string[]];
+}) => {
+  return <svg />;
+};
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components().unwrap();
+        let number = || Type::Primitive(PrimitiveType::Number);
+        let string = || Type::Primitive(PrimitiveType::String);
+        let point = Type::Tuple(vec![
+            TupleElement::new(number()).with_label("x"),
+            TupleElement::new(number()).with_label("y"),
+            TupleElement::new(string()).with_label("label").optional(),
+        ]);
+        assert_eq!(point.to_str(), "[x: number, y: number, label?: string]");
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("range".to_string()),
+            Type::Tuple(vec![
+                TupleElement::new(number()),
+                TupleElement::new(number()),
+            ]),
+        );
+        props.insert(Key("point".to_string()), point);
+        props.insert(
+            Key("path".to_string()),
+            Type::Tuple(vec![
+                TupleElement::new(string()),
+                TupleElement::new(Type::Array(Box::new(string()))).rest(),
+            ]),
+        );
+        let expect = Component::new("Chart", Props::Expand(props));
+        assert_eq!(components, vec![expect]);
+        let options = SampleOptions::new(ActionStyle::Fn);
+        assert_eq!(
+            components[0].fill_sample(&options),
+            r#"{ path: [""],point: [0, 0, ""],range: [0, 0], }"#
+        );
+        assert_eq!(
+            components[0].fill_sample(&options.with_omit_optional(true)),
+            r#"{ path: [""],point: [0, 0],range: [0, 0], }"#
+        );
+    }
+    #[test]
+    fn test_to_nested_generic() {
+        let content = r#"
+export const List = (props: {
+  items: Record<string, Array<Promise<Item>>>;
+}) => {
+  return <ul />;
+};
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components();
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("items".to_string()),
+            Type::Generic {
+                name: "Record".to_string(),
+                args: vec![
+                    Type::Primitive(PrimitiveType::String),
+                    Type::Generic {
+                        name: "Array".to_string(),
+                        args: vec![Type::Generic {
+                            name: "Promise".to_string(),
+                            args: vec![Type::Alias("Item".to_string())],
+                        }],
+                    },
+                ],
+            },
+        );
+        let expect = Component::new("List", Props::Expand(props));
+        assert_eq!(components.unwrap(), vec![expect]);
+    }
+    #[test]
+    fn test_to_nested_object() {
+        let content = r#"
+type ProfileProps = {
+  user: {
+    name: string;
+    address: { city: string; zip?: string },
+    "data-id": number
+  };
+};
+export const Profile = (props: ProfileProps) => {
+  return <div />;
+};
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components().unwrap();
+        let mut address = ObjectType::new();
+        address.insert(
+            Key("city".to_string()),
+            Type::Primitive(PrimitiveType::String),
+        );
+        address.insert_property(
+            Key("zip".to_string()),
+            Property::new(Type::Primitive(PrimitiveType::String)).optional(),
+        );
+        let mut user = ObjectType::new();
+        user.insert(
+            Key("name".to_string()),
+            Type::Primitive(PrimitiveType::String),
+        );
+        user.insert(Key("address".to_string()), Type::Object(address));
+        user.insert(
+            Key("data-id".to_string()),
+            Type::Primitive(PrimitiveType::Number),
+        );
+        let mut props = ObjectType::new();
+        props.insert(Key("user".to_string()), Type::Object(user));
+        let expect = Component::new(
+            "Profile",
+            Props::Named(NamedProps::new("ProfileProps", props)),
+        );
+        assert_eq!(components, vec![expect]);
+        assert_eq!(
+            components[0].fill_sample(&SampleOptions::new(ActionStyle::Fn)),
+            r#"{ user: { address: { city: "",zip: "", },"data-id": 0,name: "", }, }"#
+        );
+    }
+    #[test]
+    fn test_to_documented_props() {
+        let content = r#"
+type ButtonProps = {
+  /** shown in the button (required */
+  label: string;
+  // defaults to "md"
+  size?: "sm" | "md";
+};
+export interface CardProps {
+  /**
+   * nested { options }
+   */
+  options: {
+    /** dense layout */
+    dense: boolean;
+  };
+}
+export const Button = (props: ButtonProps) => <button>{props.label}</button>;
+export const Card = (props: CardProps) => <div />;
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components().unwrap();
+        let options = SampleOptions::new(ActionStyle::Fn);
+        assert_eq!(components.len(), 2);
+        assert_eq!(
+            components[0].fill_sample(&options),
+            r#"{ label: "",size: "sm", }"#
+        );
+        assert_eq!(
+            components[1].fill_sample(&options),
+            r#"{ options: { dense: false, }, }"#
+        );
+    }
+    #[test]
+    fn test_to_readonly_optional_props() {
+        let content = r#"
+type ItemProps = {
+  readonly id: string;
+  readonly label?: string;
+  readonly?: boolean;
+};
+export const Item = (props: ItemProps) => {
+  return <div />;
+};
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components().unwrap();
+        let mut props = ObjectType::new();
+        props.insert_property(
+            Key("id".to_string()),
+            Property::new(Type::Primitive(PrimitiveType::String)).readonly(),
+        );
+        props.insert_property(
+            Key("label".to_string()),
+            Property::new(Type::Primitive(PrimitiveType::String))
+                .optional()
+                .readonly(),
+        );
+        props.insert_property(
+            Key("readonly".to_string()),
+            Property::new(Type::Primitive(PrimitiveType::Boolean)).optional(),
+        );
+        let expect = Component::new("Item", Props::Named(NamedProps::new("ItemProps", props)));
+        assert_eq!(components, vec![expect]);
+        let options = SampleOptions::new(ActionStyle::Fn);
+        assert_eq!(
+            components[0].fill_sample(&options),
+            r#"{ id: "",label: "",readonly: false, }"#
+        );
+        assert_eq!(
+            components[0].fill_sample(&options.with_omit_optional(true)),
+            r#"{ id: "", }"#
+        );
     }
     #[test]
     fn test_to_func_generic() {
@@ -715,20 +2058,35 @@ export const DeleteConfirmModal = (props: {
   );
 };
 "#;
-        let content = TSXContent(content.to_string());
-        let component = content.to_component();
+        let content = TSXContent::new(content);
+        let components = content.to_components();
         let mut props = ObjectType::new();
         props.insert(
             Key("deleteHandler".to_string()),
-            Type::Alias("() => Promise<void>".to_string()),
+            Type::Function {
+                params: Vec::new(),
+                ret: Box::new(Type::Generic {
+                    name: "Promise".to_string(),
+                    args: vec![Type::Primitive(PrimitiveType::Void)],
+                }),
+            },
+        );
+        props.insert(
+            Key("open".to_string()),
+            Type::Primitive(PrimitiveType::Boolean),
         );
-        props.insert(Key("open".to_string()), Type::Alias("boolean".to_string()));
         props.insert(
             Key("setOpen".to_string()),
-            Type::Alias("React.Dispatch<React.SetStateAction<boolean>>".to_string()),
+            Type::Generic {
+                name: "React.Dispatch".to_string(),
+                args: vec![Type::Generic {
+                    name: "React.SetStateAction".to_string(),
+                    args: vec![Type::Primitive(PrimitiveType::Boolean)],
+                }],
+            },
         );
         let expect = Component::new("DeleteConfirmModal", Props::Expand(props));
-        assert_eq!(component.unwrap(), expect);
+        assert_eq!(components.unwrap(), vec![expect]);
     }
 
     #[test]
@@ -751,12 +2109,15 @@ export const RegisterButtons = (props: ButtonProps) => {
   );
 };
 "#;
-        let content = TSXContent(content.to_string());
-        let component = content.to_component();
+        let content = TSXContent::new(content);
+        let components = content.to_components();
         let mut props = ObjectType::new();
         props.insert(
             Key("generics".to_string()),
-            Type::Alias("React<Hoge>".to_string()),
+            Type::Generic {
+                name: "React".to_string(),
+                args: vec![Type::Alias("Hoge".to_string())],
+            },
         );
         props.insert(
             Key("noGenerics".to_string()),
@@ -766,7 +2127,7 @@ export const RegisterButtons = (props: ButtonProps) => {
             "RegisterButtons",
             Props::Named(NamedProps::new_object_type("ButtonProps", props)),
         );
-        assert_eq!(component.unwrap(), expect);
+        assert_eq!(components.unwrap(), vec![expect]);
     }
 
     #[test]
@@ -788,20 +2149,648 @@ export const RegisterButtons = (props: ButtonProps) => {
   );
 };
 "#;
-        let content = TSXContent(content.to_string());
-        let component = content.to_component();
+        let content = TSXContent::new(content);
+        let components = content.to_components();
         let mut props = ObjectType::new();
         props.insert(
             Key("handler".to_string()),
-            Type::Alias("() => void".to_string()),
+            Type::Function {
+                params: Vec::new(),
+                ret: Box::new(Type::Primitive(PrimitiveType::Void)),
+            },
         );
         let expect = Component::new(
             "RegisterButtons",
             Props::Named(NamedProps::new_object_type("ButtonProps", props)),
         );
-        assert_eq!(component.unwrap(), expect);
+        assert_eq!(components.unwrap(), vec![expect]);
+    }
+
+    #[test]
+    fn test_to_interface() {
+        let content = r#"
+import React from "react";
+
+export interface ButtonProps {
+  label: string;
+  size?: number;
+}
+
+export const Button = (props: ButtonProps) => {
+  return <button>{props.label}</button>;
+};
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components();
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("label".to_string()),
+            Type::Primitive(PrimitiveType::String),
+        );
+        props.insert_property(
+            Key("size".to_string()),
+            Property::new(Type::Primitive(PrimitiveType::Number)).optional(),
+        );
+        let expect = Component::new(
+            "Button",
+            Props::Named(NamedProps::new_object_type("ButtonProps", props)),
+        );
+        assert_eq!(components.unwrap(), vec![expect]);
+    }
+    #[test]
+    fn test_to_generic_interface() {
+        let content = r#"
+export interface ListProps<T extends object = {}> {
+  items: T[];
+}
+
+export const List = (props: ListProps) => {
+  return <ul />;
+};
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components();
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("items".to_string()),
+            Type::Array(Box::new(Type::Alias("T".to_string()))),
+        );
+        let expect = Component::new(
+            "List",
+            Props::Named(NamedProps::new_object_type("ListProps", props)),
+        );
+        assert_eq!(components.unwrap(), vec![expect]);
+    }
+    #[test]
+    fn test_to_unresolved_alias_sample() {
+        let content = r#"
+import { User } from "./user";
+
+interface TableProps<T> {
+  items: T[];
+  owner: User;
+}
+export function Table<T>(props: TableProps<T>) {
+  return <table />;
+}
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components().unwrap();
+        // T も読めない User も値にはならない
+        assert_eq!(
+            components[0].fill_sample(&SampleOptions::new(ActionStyle::Fn)),
+            "{ items: [undefined],owner: undefined, }"
+        );
+    }
+    #[test]
+    fn test_to_interface_extends() {
+        let content = r#"
+import React from "react";
+
+interface ButtonProps extends BaseProps, React.HTMLAttributes<HTMLButtonElement> {
+  label: string;
+}
+
+export const Button = (props: ButtonProps) => {
+  return <button>{props.label}</button>;
+};
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components();
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("label".to_string()),
+            Type::Primitive(PrimitiveType::String),
+        );
+        let expect = Component::new(
+            "Button",
+            Props::Named(NamedProps::new_intersection_type(
+                "ButtonProps",
+                vec![
+                    Type::Object(props),
+                    Type::Alias("BaseProps".to_string()),
+                    Type::Generic {
+                        name: "React.HTMLAttributes".to_string(),
+                        args: vec![Type::Alias("HTMLButtonElement".to_string())],
+                    },
+                ],
+            )),
+        );
+        assert_eq!(components.unwrap(), vec![expect]);
+    }
+    #[test]
+    fn test_to_utility_types() {
+        let content = r#"
+type BaseProps = {
+  label: string;
+  onClick: () => void;
+};
+interface Config {
+  dense: boolean;
+  color: string;
+}
+
+interface ButtonProps extends Omit<BaseProps, "onClick"> {
+  config: Partial<Config>;
+  theme: Pick<Config, "color">;
+}
+
+export const Button = (props: ButtonProps) => {
+  return <button>{props.label}</button>;
+};
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components().unwrap();
+        assert_eq!(
+            components[0].fill_sample(&SampleOptions::new(ActionStyle::Fn)),
+            r#"{ config: { color: "",dense: false, },label: "",theme: { color: "", }, }"#
+        );
+        assert_eq!(
+            components[0]
+                .fill_sample(&SampleOptions::new(ActionStyle::Fn).with_omit_optional(true)),
+            r#"{ config: {  },label: "",theme: { color: "", }, }"#
+        );
+    }
+    #[test]
+    fn test_to_components() {
+        let content = r#"
+import React from "react";
+
+export type CardProps = {
+  title: string;
+};
+
+export const useCard = () => {
+  return {};
+};
+
+export const Card = (props: CardProps) => {
+  return <div>{props.title}</div>;
+};
+
+export const CardHeader = (props: CardProps) => {
+  return <div>{props.title}</div>;
+};
+
+export const cardClassName = "card";
+
+export function CardFooter() {
+  return <div />;
+}
+
+export default function CardBody(props: { text: string }) {
+  return <div>{props.text}</div>;
+}
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components();
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("title".to_string()),
+            Type::Primitive(PrimitiveType::String),
+        );
+        let mut body_props = ObjectType::new();
+        body_props.insert(
+            Key("text".to_string()),
+            Type::Primitive(PrimitiveType::String),
+        );
+        let expect = vec![
+            Component::new(
+                "Card",
+                Props::Named(NamedProps::new_object_type("CardProps", props.clone())),
+            ),
+            Component::new(
+                "CardHeader",
+                Props::Named(NamedProps::new_object_type("CardProps", props)),
+            ),
+            Component::new("CardFooter", Props::Expand(ObjectType::new())),
+            Component::new("CardBody", Props::Expand(body_props)).default_export(),
+        ];
+        assert_eq!(components.unwrap(), expect);
+    }
+    #[test]
+    fn test_to_props_declared_after_component() {
+        let content = r#"
+import React from "react";
+
+export const Button = (props: ButtonProps) => {
+  return <button>{props.label}</button>;
+};
+
+export const Footer: React.FC<FooterProps> = (props) => {
+  return <footer>{props.label}</footer>;
+};
+
+type ButtonProps = {
+  label: string;
+};
+
+interface FooterProps {
+  label: string;
+}
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components();
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("label".to_string()),
+            Type::Primitive(PrimitiveType::String),
+        );
+        let expect = vec![
+            Component::new(
+                "Button",
+                Props::Named(NamedProps::new_object_type("ButtonProps", props.clone())),
+            ),
+            Component::new(
+                "Footer",
+                Props::Named(NamedProps::new_object_type("FooterProps", props)),
+            ),
+        ];
+        assert_eq!(components.unwrap(), expect);
     }
+    #[test]
+    fn test_to_imported_props() {
+        let dir_name = "test_to_imported_props";
+        std::fs::create_dir_all(format!("{dir_name}/types")).unwrap();
+        std::fs::write(
+            format!("{dir_name}/types/button.ts"),
+            r#"
+export type ButtonProps = {
+  label: string;
+};
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            format!("{dir_name}/types/index.ts"),
+            r#"
+export * from "./button";
+export { CardProps as BaseCardProps } from "./card";
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            format!("{dir_name}/types/card.ts"),
+            r#"
+export interface CardProps {
+  title: string;
+}
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            format!("{dir_name}/Button.tsx"),
+            r#"
+import React from "react";
+import { ButtonProps } from "./types";
+import type { BaseCardProps as CardProps } from "./types";
+
+export const Button = (props: ButtonProps) => {
+  return <button>{props.label}</button>;
+};
 
+export const Card = (props: CardProps) => {
+  return <div>{props.title}</div>;
+};
+"#,
+        )
+        .unwrap();
+        let content = TSXContent::from_file(format!("{dir_name}/Button.tsx")).unwrap();
+        let components = content.to_components();
+        std::fs::remove_dir_all(dir_name).unwrap();
+
+        let mut button_props = ObjectType::new();
+        button_props.insert(
+            Key("label".to_string()),
+            Type::Primitive(PrimitiveType::String),
+        );
+        let mut card_props = ObjectType::new();
+        card_props.insert(
+            Key("title".to_string()),
+            Type::Primitive(PrimitiveType::String),
+        );
+        let expect = vec![
+            Component::new(
+                "Button",
+                Props::Named(
+                    NamedProps::new_object_type("ButtonProps", button_props)
+                        .imported("ButtonProps", Import::new("ButtonProps", "./types")),
+                ),
+            ),
+            Component::new(
+                "Card",
+                Props::Named(
+                    NamedProps::new_object_type("CardProps", card_props)
+                        .imported("CardProps", Import::new("BaseCardProps", "./types")),
+                ),
+            ),
+        ];
+        assert_eq!(components.unwrap(), expect);
+    }
+    #[test]
+    fn test_to_unparsable_imported_props() {
+        let dir_name = "test_to_unparsable_imported_props";
+        std::fs::create_dir_all(dir_name).unwrap();
+        std::fs::write(
+            format!("{dir_name}/types.ts"),
+            r#"
+export type ButtonProps = {
+  label: <div>;
+};
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            format!("{dir_name}/Button.tsx"),
+            r#"
+import { ButtonProps } from "./types";
+
+export const Button = (props: ButtonProps) => {
+  return <button>{props.label}</button>;
+};
+export const Icon = () => <svg />;
+"#,
+        )
+        .unwrap();
+        let content = TSXContent::from_file(format!("{dir_name}/Button.tsx")).unwrap();
+        let (components, skipped) = content.read_components().unwrap();
+        std::fs::remove_dir_all(dir_name).unwrap();
+
+        // types.ts は読めないので ButtonProps は解決されないまま残る
+        let expect = vec![
+            Component::new(
+                "Button",
+                Props::Named(NamedProps::new("ButtonProps", ObjectType::new())),
+            ),
+            Component::new("Icon", Props::Expand(ObjectType::new())),
+        ];
+        assert_eq!(components, expect);
+        // 読めなかったファイルは呼び出し側が報告する
+        let skipped: Vec<String> = skipped.iter().map(|err| err.to_string()).collect();
+        assert_eq!(
+            skipped,
+            vec![format!("{dir_name}/types.ts:3:10: unexpected `<`")]
+        );
+    }
+    #[test]
+    fn test_to_enum_props() {
+        let content = r#"
+export enum ButtonSize {
+  Small = "sm",
+  Large = "lg",
+}
+export const enum Tone { Light, Dark = 1 << 1, "high-contrast" }
+export const Button = (props: { size: ButtonSize; tone?: Tone }) => {
+  return <button />;
+};
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components().unwrap();
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("size".to_string()),
+            Type::Enum(EnumType::new(
+                "ButtonSize",
+                vec!["Small".to_string(), "Large".to_string()],
+            )),
+        );
+        props.insert_property(
+            Key("tone".to_string()),
+            Property::new(Type::Enum(EnumType::new(
+                "Tone",
+                vec![
+                    "Light".to_string(),
+                    "Dark".to_string(),
+                    "high-contrast".to_string(),
+                ],
+            )))
+            .optional(),
+        );
+        let expect = Component::new("Button", Props::Expand(props));
+        assert_eq!(components, vec![expect]);
+        assert_eq!(
+            components[0].fill_sample(&SampleOptions::new(ActionStyle::Fn)),
+            "{ size: ButtonSize.Small,tone: Tone.Light, }"
+        );
+        assert_eq!(
+            components[0].enum_imports(),
+            vec![(None, "ButtonSize".to_string()), (None, "Tone".to_string())]
+        );
+        assert_eq!(
+            components[0].arg_types()[1].1.to_str("tone"),
+            r#"{ control: "select", options: [Tone.Light, Tone.Dark, Tone["high-contrast"]] }"#
+        );
+    }
+    #[test]
+    fn test_to_imported_enum_props() {
+        let dir_name = "test_to_imported_enum_props";
+        std::fs::create_dir_all(format!("{dir_name}/types")).unwrap();
+        std::fs::write(
+            format!("{dir_name}/types/index.ts"),
+            r#"
+export enum Size {
+  Small,
+  Large,
+}
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            format!("{dir_name}/Button.tsx"),
+            r#"
+import { Size as ButtonSize } from "./types";
+
+type ButtonProps = {
+  size: ButtonSize;
+};
+export const Button = (props: ButtonProps) => {
+  return <button />;
+};
+"#,
+        )
+        .unwrap();
+        let content = TSXContent::from_file(format!("{dir_name}/Button.tsx")).unwrap();
+        let components = content.to_components();
+        std::fs::remove_dir_all(dir_name).unwrap();
+
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("size".to_string()),
+            Type::Enum(
+                EnumType::new("Size", vec!["Small".to_string(), "Large".to_string()])
+                    .imported("ButtonSize", Import::new("Size", "./types")),
+            ),
+        );
+        let components = components.unwrap();
+        assert_eq!(
+            components,
+            vec![Component::new(
+                "Button",
+                Props::Named(NamedProps::new("ButtonProps", props)),
+            )]
+        );
+        assert_eq!(
+            components[0].enum_imports(),
+            vec![(Some("./types"), "Size as ButtonSize".to_string())]
+        );
+    }
+    #[test]
+    fn test_to_component_span() {
+        let content = r#"
+type Props = {
+  label: string;
+};
+
+export const Button = (props: Props) => {
+  return <button>{props.label}</button>;
+};
+"#;
+        let content = TSXContent::new(content);
+        let component = content.to_components().unwrap().remove(0);
+        assert_eq!(
+            component.span(),
+            Some(Span::new(
+                Position::new(50, 6, 14),
+                Position::new(56, 6, 20)
+            ))
+        );
+        assert_eq!(
+            component.props_span(),
+            Some(Span::new(Position::new(6, 2, 6), Position::new(11, 2, 11)))
+        );
+    }
+    #[test]
+    fn test_parse_error() {
+        let content = r#"
+type Props = {
+  label: <div>;
+};
+
+export const Button = (props: Props) => {
+  return <button>{props.label}</button>;
+};
+"#;
+        let content = TSXContent::new(content);
+        let err = content.to_components().unwrap_err();
+        assert_eq!(err.to_string(), "3:10: unexpected `<`");
+        assert_eq!(
+            err.with_file("Button.tsx").to_string(),
+            "Button.tsx:3:10: unexpected `<`"
+        );
+    }
+    #[test]
+    fn test_props_parse_error() {
+        let content = r#"
+export const Chip: FC<{ label: string; size: <div> }> = ({ label }) => <span />;
+"#;
+        let content = TSXContent::new(content);
+        // object の途中で止めずにエラーにする
+        let err = content.to_components().unwrap_err();
+        assert_eq!(err.to_string(), "2:46: unexpected `<`");
+    }
+    #[test]
+    fn test_to_unusual_signatures() {
+        let content = r#"
+import React, { FC } from "react";
+
+type Props = { label: string };
+
+export const Spacer: React.FC = () => <hr />;
+export const Chip: FC<{ label: string; onDelete?: () => void }> = (props) => {
+  return <span>{props.label}</span>;
+};
+export const Tag = ({ label, size = "md" }: Props) => {
+  return <span>{label}</span>;
+};
+export function Cell<T>({ value }: { value: T }) {
+  return <td>{String(value)}</td>;
+}
+export const Button = (props: Props) => {
+  return <button>{props.label}</button>;
+};
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components().unwrap();
+        let mut props = ObjectType::new();
+        props.insert(
+            Key("label".to_string()),
+            Type::Primitive(PrimitiveType::String),
+        );
+        let mut chip = props.clone();
+        chip.insert_property(
+            Key("onDelete".to_string()),
+            Property::new(Type::Function {
+                params: Vec::new(),
+                ret: Box::new(Type::Primitive(PrimitiveType::Void)),
+            })
+            .optional(),
+        );
+        let mut cell = ObjectType::new();
+        cell.insert(Key("value".to_string()), Type::Alias("T".to_string()));
+        assert_eq!(
+            components,
+            vec![
+                Component::new("Spacer", Props::Expand(ObjectType::new())),
+                Component::new("Chip", Props::Expand(chip)),
+                Component::new(
+                    "Tag",
+                    Props::Named(NamedProps::new_object_type("Props", props.clone()))
+                ),
+                Component::new("Cell", Props::Expand(cell)),
+                Component::new(
+                    "Button",
+                    Props::Named(NamedProps::new_object_type("Props", props))
+                ),
+            ]
+        );
+    }
+    #[test]
+    fn test_to_generic_and_later_exports() {
+        let content = r#"
+type ListProps<T> = { items: T[] };
+type Props = { label: string };
+
+export const List = <T,>(props: ListProps<T>) => <ul />;
+export const Names: FC<ListProps<string>> = (props) => <ul />;
+const Bottom = (props: Props) => <footer />;
+function Header(props: Props) {
+  return <header />;
+}
+export const Button = (props: Props) => <button />;
+const helper = (value: string) => value;
+
+export default Bottom;
+export { Header as PageHeader, helper };
+export default Button;
+"#;
+        let content = TSXContent::new(content);
+        let components = content.to_components().unwrap();
+        let names: Vec<(&str, String, bool)> = components
+            .iter()
+            .map(|component| {
+                (
+                    component.name.as_str(),
+                    component.props_str(),
+                    component.is_default_export(),
+                )
+            })
+            .collect();
+        // export const された Button は export default Button で重複させない
+        assert_eq!(
+            names,
+            vec![
+                ("List", "ListProps<unknown>".to_string(), false),
+                ("Names", "ListProps<string>".to_string(), false),
+                ("Button", "Props".to_string(), false),
+                ("Bottom", "Props".to_string(), true),
+                ("PageHeader", "Props".to_string(), false),
+            ]
+        );
+        assert_eq!(
+            components[0].fill_sample(&SampleOptions::new(ActionStyle::Fn)),
+            "{ items: [undefined], }"
+        );
+    }
     #[test]
     fn test_to_react_dot_fc() {
         let content = r#"
@@ -816,18 +2805,18 @@ export const Footer:React.FC<Props> = (props) => {
   return <AppFooter></AppFooter>;
 };
 "#;
-        let content = TSXContent(content.to_string());
-        let component = content.to_component();
+        let content = TSXContent::new(content);
+        let components = content.to_components();
         let mut obj = ObjectType::new();
         obj.insert(
             Key("timeOut".to_string()),
-            Type::Alias("number".to_string()),
+            Type::Primitive(PrimitiveType::Number),
         );
         let expect = Component::new(
             "Footer",
             Props::Named(NamedProps::new_object_type("Props", obj)),
         );
-        assert_eq!(component.unwrap(), expect);
+        assert_eq!(components.unwrap(), vec![expect]);
     }
     #[test]
     fn test_to_component2() {
@@ -839,10 +2828,10 @@ export const Footer = () => {
   return <AppFooter></AppFooter>;
 };
 "#;
-        let content = TSXContent(content.to_string());
-        let component = content.to_component();
+        let content = TSXContent::new(content);
+        let components = content.to_components();
         let expect = Component::new("Footer", Props::Expand(ObjectType::new()));
-        assert_eq!(component.unwrap(), expect);
+        assert_eq!(components.unwrap(), vec![expect]);
     }
 
     #[test]
@@ -875,23 +2864,26 @@ export const Footer = () => {
       );
     };
     "#;
-        let content = TSXContent(content.to_string());
-        let component = content.to_component();
+        let content = TSXContent::new(content);
+        let components = content.to_components();
         let mut props = ObjectType::new();
         props.insert(
             Key("timeOut".to_string()),
-            Type::Alias("number".to_string()),
+            Type::Primitive(PrimitiveType::Number),
+        );
+        props.insert_property(
+            Key("errorMessage".to_string()),
+            Property::new(Type::Primitive(PrimitiveType::String)).optional(),
         );
         props.insert(
-            Key("errorMessage?".to_string()),
-            Type::Alias("string".to_string()),
+            Key("size".to_string()),
+            Type::Primitive(PrimitiveType::Number),
         );
-        props.insert(Key("size".to_string()), Type::Alias("number".to_string()));
         let expect = Component::new(
             "ErrorAlert",
             Props::Named(NamedProps::new_object_type("Props", props)),
         );
 
-        assert_eq!(component.unwrap(), expect);
+        assert_eq!(components.unwrap(), vec![expect]);
     }
 }
@@ -0,0 +1,255 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    component::{EnumType, Props, TSXContent},
+    parser::{ComponentPartsParser, ParseError},
+    tsconfig::TsConfig,
+};
+
+pub(super) type TypeName = String;
+
+// import { NAME as LOCAL } from "MODULE"
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Import {
+    pub name: TypeName,
+    pub module: String,
+}
+impl Import {
+    pub fn new(name: impl Into<TypeName>, module: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            module: module.into(),
+        }
+    }
+}
+
+// 1ファイル内の宣言を全て確保しておくもの
+#[derive(Debug, Default)]
+pub(super) struct SymbolTable {
+    types: HashMap<TypeName, Props>,
+    enums: HashMap<TypeName, EnumType>,
+    // LOCAL -> import { NAME as LOCAL } from "MODULE"
+    imports: HashMap<TypeName, Import>,
+    // EXPORTED -> export { NAME as EXPORTED } from "MODULE"
+    reexports: HashMap<TypeName, Import>,
+    // export * from "MODULE"
+    star_reexports: Vec<String>,
+}
+
+impl SymbolTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn insert_type(&mut self, name: impl Into<TypeName>, props: Props) {
+        self.types.insert(name.into(), props);
+    }
+    pub fn get_type(&self, name: &str) -> Option<&Props> {
+        self.types.get(name)
+    }
+    pub fn insert_enum(&mut self, name: impl Into<TypeName>, enum_type: EnumType) {
+        self.enums.insert(name.into(), enum_type);
+    }
+    pub fn get_enum(&self, name: &str) -> Option<&EnumType> {
+        self.enums.get(name)
+    }
+    pub fn insert_import(&mut self, local: impl Into<TypeName>, import: Import) {
+        self.imports.insert(local.into(), import);
+    }
+    pub fn get_import(&self, local: &str) -> Option<&Import> {
+        self.imports.get(local)
+    }
+    pub fn insert_reexport(&mut self, exported: impl Into<TypeName>, import: Import) {
+        self.reexports.insert(exported.into(), import);
+    }
+    pub fn insert_star_reexport(&mut self, module: impl Into<String>) {
+        self.star_reexports.push(module.into());
+    }
+}
+
+// importされているファイルを読み込み, exportされている型を探すもの
+pub(super) struct ModuleLoader<'a> {
+    modules: HashMap<PathBuf, SymbolTable>,
+    // resolves aliased modules such as @/components/Button
+    tsconfig: Option<&'a TsConfig>,
+    // errors of the files that could not be parsed, reported by the caller
+    skipped: Vec<ParseError>,
+}
+
+impl<'a> ModuleLoader<'a> {
+    pub fn new(tsconfig: Option<&'a TsConfig>) -> Self {
+        Self {
+            modules: HashMap::new(),
+            tsconfig,
+            skipped: Vec::new(),
+        }
+    }
+    pub fn into_skipped(self) -> Vec<ParseError> {
+        self.skipped
+    }
+    // from のファイルから import { name } from "module" された型を探す
+    pub fn find_exported_type(&mut self, from: &Path, module: &str, name: &str) -> Option<Props> {
+        let mut visited = Vec::new();
+        self.find_exported(from, module, name, SymbolTable::get_type, &mut visited)
+    }
+    // from のファイルから import { name } from "module" された enum を探す
+    pub fn find_exported_enum(
+        &mut self,
+        from: &Path,
+        module: &str,
+        name: &str,
+    ) -> Option<EnumType> {
+        let mut visited = Vec::new();
+        self.find_exported(from, module, name, SymbolTable::get_enum, &mut visited)
+    }
+    fn find_exported<T: Clone>(
+        &mut self,
+        from: &Path,
+        module: &str,
+        name: &str,
+        get: for<'s> fn(&'s SymbolTable, &str) -> Option<&'s T>,
+        visited: &mut Vec<(PathBuf, TypeName)>,
+    ) -> Option<T> {
+        let path = resolve_module_path(from, module, self.tsconfig)?;
+        // export * from で循環している場合
+        if visited.contains(&(path.clone(), name.to_string())) {
+            return None;
+        }
+        visited.push((path.clone(), name.to_string()));
+
+        let symbols = self.load(&path);
+        if let Some(found) = get(symbols, name) {
+            return Some(found.clone());
+        }
+        let mut next = Vec::new();
+        // import { NAME } from "MODULE"; export { NAME };
+        if let Some(import) = symbols.reexports.get(name).or(symbols.imports.get(name)) {
+            next.push((import.module.clone(), import.name.clone()));
+        }
+        for module in &symbols.star_reexports {
+            next.push((module.clone(), name.to_string()));
+        }
+        next.into_iter()
+            .find_map(|(module, name)| self.find_exported(&path, &module, &name, get, visited))
+    }
+    fn load(&mut self, path: &Path) -> &SymbolTable {
+        if !self.modules.contains_key(path) {
+            // 読めないファイルや parse できないファイルは何も export していないものとして扱う
+            // import している component は props が解決されないまま残る
+            let symbols = match TSXContent::from_file(path) {
+                Ok(content) => match ComponentPartsParser::new(&content).read_symbols() {
+                    Ok(symbols) => symbols,
+                    Err(err) => {
+                        self.skipped.push(err.with_file(path));
+                        SymbolTable::new()
+                    }
+                },
+                Err(_) => SymbolTable::new(),
+            };
+            self.modules.insert(path.to_path_buf(), symbols);
+        }
+        &self.modules[path]
+    }
+}
+
+// TypeScriptと同じ順番でimportを解決する
+// 相対パスでないものは tsconfig.json の paths, baseUrl を使う
+pub(super) fn resolve_module_path(
+    from: &Path,
+    module: &str,
+    tsconfig: Option<&TsConfig>,
+) -> Option<PathBuf> {
+    if module.starts_with("./") || module.starts_with("../") {
+        return resolve_file(&from.parent().unwrap_or(Path::new("")).join(module));
+    }
+    tsconfig?
+        .candidates(module)
+        .iter()
+        .find_map(|base| resolve_file(base))
+}
+
+// ./x -> ./x.ts, ./x.tsx, ./x.d.ts, ./x/index.ts, ./x/index.tsx
+fn resolve_file(base: &Path) -> Option<PathBuf> {
+    if base.is_file() {
+        return Some(base.to_path_buf());
+    }
+    let candidates = [
+        base.with_file_name(format!("{}.ts", file_name(base)?)),
+        base.with_file_name(format!("{}.tsx", file_name(base)?)),
+        base.with_file_name(format!("{}.d.ts", file_name(base)?)),
+        base.join("index.ts"),
+        base.join("index.tsx"),
+    ];
+    candidates.into_iter().find(|candidate| candidate.is_file())
+}
+
+fn file_name(path: &Path) -> Option<&str> {
+    path.file_name().and_then(|name| name.to_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::{create_dir_all, create_file, remove_dir};
+
+    #[test]
+    fn test_resolve_module_path() {
+        let dir_name = "test_resolve_module_path";
+        create_dir_all(format!("{dir_name}/components").as_str());
+        create_dir_all(format!("{dir_name}/types").as_str());
+        create_file(format!("{dir_name}/components/Button.tsx").as_str(), "");
+        create_file(format!("{dir_name}/components/props.ts").as_str(), "");
+        create_file(format!("{dir_name}/types/index.ts").as_str(), "");
+
+        let from = PathBuf::from(format!("{dir_name}/components/Button.tsx"));
+        let props = resolve_module_path(&from, "./props", None);
+        let button = resolve_module_path(&from, "./Button", None);
+        let types = resolve_module_path(&from, "../types", None);
+        let react = resolve_module_path(&from, "react", None);
+        let missing = resolve_module_path(&from, "./missing", None);
+        remove_dir(dir_name);
+
+        assert_eq!(
+            props,
+            Some(PathBuf::from(format!("{dir_name}/components/props.ts")))
+        );
+        assert_eq!(
+            button,
+            Some(PathBuf::from(format!("{dir_name}/components/Button.tsx")))
+        );
+        assert_eq!(
+            types,
+            Some(PathBuf::from(format!(
+                "{dir_name}/components/../types/index.ts"
+            )))
+        );
+        assert_eq!(react, None);
+        assert_eq!(missing, None);
+    }
+    #[test]
+    fn test_resolve_aliased_module_path() {
+        let dir_name = "test_resolve_aliased_module_path";
+        create_dir_all(format!("{dir_name}/src/components").as_str());
+        create_dir_all(format!("{dir_name}/src/types").as_str());
+        create_file(format!("{dir_name}/src/components/Button.tsx").as_str(), "");
+        create_file(format!("{dir_name}/src/types/index.ts").as_str(), "");
+        create_file(
+            format!("{dir_name}/tsconfig.json").as_str(),
+            r#"{ "compilerOptions": { "baseUrl": ".", "paths": { "~/*": ["src/*"] } } }"#,
+        );
+
+        let tsconfig = TsConfig::find(dir_name).unwrap();
+        let from = PathBuf::from(format!("{dir_name}/src/components/Button.tsx"));
+        let types = resolve_module_path(&from, "~/types", tsconfig.as_ref());
+        let base_url = resolve_module_path(&from, "src/components/Button", tsconfig.as_ref());
+        let react = resolve_module_path(&from, "react", tsconfig.as_ref());
+        let root = std::path::absolute(dir_name).unwrap();
+        remove_dir(dir_name);
+
+        assert_eq!(types, Some(root.join("src/types/index.ts")));
+        assert_eq!(base_url, Some(root.join("src/components/Button.tsx")));
+        assert_eq!(react, None);
+    }
+}
@@ -0,0 +1,15 @@
+// テストで使うファイルやディレクトリを作る, 消すもの
+use std::path::Path;
+
+pub(crate) fn create_dir_all(dir_name: &str) {
+    std::fs::create_dir_all(dir_name).unwrap();
+}
+pub(crate) fn create_file(file_name: &str, content: &str) {
+    std::fs::write(file_name, content).unwrap();
+}
+pub(crate) fn remove_dir(dir_name: &str) {
+    let path: &Path = dir_name.as_ref();
+    if path.exists() {
+        std::fs::remove_dir_all(dir_name).unwrap();
+    }
+}
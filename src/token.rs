@@ -1,13 +1,50 @@
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub(super) struct TSXToken {
     pub token_type: TSXTokenType,
     pub literal: String,
+    pub span: Span,
 }
 impl TSXToken {
     pub fn new(token_type: TSXTokenType, literal: impl Into<String>) -> Self {
         Self {
             token_type,
             literal: literal.into(),
+            span: Span::default(),
+        }
+    }
+}
+// span is where the token was found, not what the token is
+impl PartialEq for TSXToken {
+    fn eq(&self, other: &Self) -> bool {
+        self.token_type == other.token_type && self.literal == other.literal
+    }
+}
+
+// range of source text, start is inclusive and end is exclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+impl Span {
+    pub fn new(start: Position, end: Position) -> Self {
+        Self { start, end }
+    }
+}
+
+// byte offset and 1-based line and column of a char
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct Position {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+impl Position {
+    pub fn new(offset: usize, line: usize, column: usize) -> Self {
+        Self {
+            offset,
+            line,
+            column,
         }
     }
 }
@@ -15,7 +52,10 @@ impl TSXToken {
 #[derive(Debug, PartialEq, Clone)]
 pub enum TSXTokenType {
     Comment,
+    // /* */ is lexed as one Comment
+    #[allow(dead_code)]
     StartDocComment,
+    #[allow(dead_code)]
     EndDocComment,
     Increment,
     Add,
@@ -70,18 +110,31 @@ pub enum TSXTokenType {
     NullishCoalescing,
     Type,
     StringLiteral,
+    TemplateLiteral,
+    RegexLiteral,
     Class,
     Export,
     Import,
     Default,
     Arrow,
+    // string, number and boolean are lexed as Ident and read as types by the parser
+    #[allow(dead_code)]
     String,
+    #[allow(dead_code)]
     Number,
+    #[allow(dead_code)]
     Boolean,
     Undefined,
+    Null,
+    Void,
     And,
+    Interface,
+    Extends,
+    Enum,
 }
 impl TSXTokenType {
+    // the source text of the token type, not used by the parser
+    #[allow(dead_code)]
     pub fn to_str(&self) -> &'static str {
         match self {
             TSXTokenType::And => "&",
@@ -141,6 +194,8 @@ impl TSXTokenType {
             TSXTokenType::NullishCoalescing => "??",
             TSXTokenType::Type => "type",
             TSXTokenType::StringLiteral => "string",
+            TSXTokenType::TemplateLiteral => "template",
+            TSXTokenType::RegexLiteral => "regex",
             TSXTokenType::Class => "class",
             TSXTokenType::Export => "export",
             TSXTokenType::Import => "import",
@@ -150,7 +205,11 @@ impl TSXTokenType {
             TSXTokenType::Number => "number",
             TSXTokenType::Boolean => "boolean",
             TSXTokenType::Undefined => "undefined",
-            TSXTokenType::From => "from",
+            TSXTokenType::Null => "null",
+            TSXTokenType::Void => "void",
+            TSXTokenType::Interface => "interface",
+            TSXTokenType::Extends => "extends",
+            TSXTokenType::Enum => "enum",
         }
     }
 }
@@ -0,0 +1,612 @@
+use std::{
+    io,
+    iter::Peekable,
+    path::{Component, Path, PathBuf},
+    str::Chars,
+};
+
+const TSCONFIG: &str = "tsconfig.json";
+
+// tsconfig.json の compilerOptions.baseUrl と paths
+// extends されている設定は読み込んだ上で上書きしてある
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct TsConfig {
+    base_url: Option<PathBuf>,
+    // "@/*": ["./src/*"]
+    paths: Vec<(String, Vec<String>)>,
+    // paths are relative to baseUrl, or to the tsconfig.json declaring them
+    paths_dir: PathBuf,
+    // extends that could not be read and are used without, reported by the caller
+    skipped: Vec<String>,
+}
+
+impl TsConfig {
+    // root から親ディレクトリへ向かって一番近い tsconfig.json を読む
+    // .git か package.json のあるディレクトリより上は別の project なので探さない
+    pub fn find(root: impl AsRef<Path>) -> Result<Option<Self>, io::Error> {
+        let root = absolute(root.as_ref())?;
+        let mut dir = if root.is_file() {
+            root.parent()
+        } else {
+            Some(root.as_path())
+        };
+        while let Some(current) = dir {
+            let path = current.join(TSCONFIG);
+            if path.is_file() {
+                return Self::from_file(path).map(Some);
+            }
+            if current.join(".git").exists() || current.join("package.json").is_file() {
+                break;
+            }
+            dir = current.parent();
+        }
+        Ok(None)
+    }
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, io::Error> {
+        let mut config = Self::default();
+        config.extend_from(&absolute(path.as_ref())?, &mut Vec::new())?;
+        Ok(config)
+    }
+    // "path: message" of the extends that were skipped
+    pub fn skipped(&self) -> &[String] {
+        &self.skipped
+    }
+    // extends されている設定を先に読み, その後に自身の設定で上書きする
+    fn extend_from(&mut self, path: &Path, extending: &mut Vec<PathBuf>) -> Result<(), io::Error> {
+        if extending.iter().any(|extended| extended == path) {
+            return Err(invalid(path, "circular extends"));
+        }
+        extending.push(path.to_path_buf());
+        let extended = self.read_config(path, extending);
+        extending.pop();
+        extended
+    }
+    fn read_config(&mut self, path: &Path, extending: &mut Vec<PathBuf>) -> Result<(), io::Error> {
+        let source = std::fs::read_to_string(path)?;
+        let json = JsonParser::new(&source)
+            .parse()
+            .map_err(|message| invalid(path, &message))?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+
+        let extends = match json.get("extends") {
+            Some(Json::String(extends)) => vec![extends.as_str()],
+            Some(extends) => extends.as_array().iter().filter_map(Json::as_str).collect(),
+            None => Vec::new(),
+        };
+        // 読めない extends はその設定を使わずに続ける
+        for extends in extends {
+            let extended = match resolve_extends(dir, extends) {
+                Some(extended) => self.extend_from(&extended, extending),
+                None => Err(invalid(path, &format!("not found extends = {}", extends))),
+            };
+            if let Err(err) = extended {
+                self.skipped.push(err.to_string());
+            }
+        }
+
+        if let Some(options) = json.get("compilerOptions") {
+            if let Some(base_url) = options.get("baseUrl").and_then(Json::as_str) {
+                self.base_url = Some(normalize(&dir.join(base_url)));
+            }
+            if let Some(Json::Object(paths)) = options.get("paths") {
+                self.paths = paths
+                    .iter()
+                    .map(|(pattern, targets)| {
+                        let targets = targets
+                            .as_array()
+                            .iter()
+                            .filter_map(Json::as_str)
+                            .map(|target| target.to_string())
+                            .collect();
+                        (pattern.clone(), targets)
+                    })
+                    .collect();
+                self.paths_dir = dir.to_path_buf();
+            }
+        }
+        Ok(())
+    }
+    // 相対パスでない module が指すファイルの候補, 拡張子は付いていない
+    // TypeScriptと同じく一番長く一致した paths を先に, 最後に baseUrl を試す
+    pub fn candidates(&self, module: &str) -> Vec<PathBuf> {
+        let mut candidates = Vec::new();
+        if let Some((targets, matched)) = self.match_paths(module) {
+            let dir = self.base_url.as_deref().unwrap_or(&self.paths_dir);
+            for target in targets {
+                candidates.push(normalize(&dir.join(target.replacen('*', matched, 1))));
+            }
+        }
+        if let Some(base_url) = &self.base_url {
+            candidates.push(normalize(&base_url.join(module)));
+        }
+        candidates
+    }
+    // (TARGETS, the part of module matched by *)
+    fn match_paths<'a>(&self, module: &'a str) -> Option<(&[String], &'a str)> {
+        let mut matched: Option<(usize, &[String], &str)> = None;
+        for (pattern, targets) in &self.paths {
+            let Some((prefix, suffix)) = pattern.split_once('*') else {
+                if pattern == module {
+                    return Some((targets, ""));
+                }
+                continue;
+            };
+            if module.len() < prefix.len() + suffix.len()
+                || !module.starts_with(prefix)
+                || !module.ends_with(suffix)
+            {
+                continue;
+            }
+            if matched.is_none_or(|(len, _, _)| prefix.len() > len) {
+                let star = &module[prefix.len()..module.len() - suffix.len()];
+                matched = Some((prefix.len(), targets, star));
+            }
+        }
+        matched.map(|(_, targets, star)| (targets, star))
+    }
+    // file を paths の別名で import する時の module
+    // src/components/Button.tsx -> @/components/Button
+    pub fn module_specifier(&self, file: impl AsRef<Path>) -> Option<String> {
+        let file = normalize(&absolute(file.as_ref()).ok()?.with_extension(""));
+        let dir = self.base_url.as_deref().unwrap_or(&self.paths_dir);
+        let file_str = file.to_str()?;
+        for (pattern, targets) in &self.paths {
+            // "*" は全てに一致してしまうので別名として使わない
+            if pattern == "*" {
+                continue;
+            }
+            for target in targets {
+                let target = normalize(&dir.join(target));
+                let target = target.to_str()?;
+                let module = match (pattern.split_once('*'), target.split_once('*')) {
+                    (Some((prefix, suffix)), Some((target_prefix, target_suffix))) => {
+                        let Some(star) = file_str
+                            .strip_prefix(target_prefix)
+                            .and_then(|rest| rest.strip_suffix(target_suffix))
+                        else {
+                            continue;
+                        };
+                        format!("{}{}{}", prefix, star, suffix)
+                    }
+                    (None, None) if Path::new(target).with_extension("") == file => {
+                        return Some(pattern.clone());
+                    }
+                    _ => continue,
+                };
+                // より長く一致する別の paths に取られていないか確かめる
+                if self.candidates(&module).first() == Some(&file) {
+                    return Some(module);
+                }
+            }
+        }
+        None
+    }
+}
+
+// extends: "./tsconfig.base.json" or "@tsconfig/node16/tsconfig.json"
+fn resolve_extends(dir: &Path, extends: &str) -> Option<PathBuf> {
+    let with_json = |path: PathBuf| {
+        [
+            path.clone(),
+            PathBuf::from(format!("{}.json", path.to_str()?)),
+            path.join(TSCONFIG),
+        ]
+        .into_iter()
+        .find(|candidate| candidate.is_file())
+    };
+    if extends.starts_with("./") || extends.starts_with("../") || Path::new(extends).is_absolute() {
+        return with_json(normalize(&dir.join(extends)));
+    }
+    dir.ancestors()
+        .find_map(|ancestor| with_json(ancestor.join("node_modules").join(extends)))
+}
+
+fn absolute(path: &Path) -> Result<PathBuf, io::Error> {
+    std::path::absolute(path).map(|path| normalize(&path))
+}
+
+// ./ と ../ をファイルシステムを見ずに取り除く
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if normalized.file_name().is_some() => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+fn invalid(path: &Path, message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {}", path.display(), message),
+    )
+}
+
+// tsconfig.json はコメントと末尾のカンマが許されたJSON
+#[derive(Debug, Clone, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            // 同じkeyが複数ある場合は後勝ち
+            Self::Object(entries) => entries.iter().rev().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+    fn as_array(&self) -> &[Json] {
+        match self {
+            Self::Array(values) => values,
+            _ => &[],
+        }
+    }
+}
+
+struct JsonParser<'a> {
+    input: Peekable<Chars<'a>>,
+}
+
+impl<'a> JsonParser<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            input: source.chars().peekable(),
+        }
+    }
+    fn parse(mut self) -> Result<Json, String> {
+        let value = self.parse_value()?;
+        self.skip_whitespace()?;
+        match self.input.next() {
+            None => Ok(value),
+            c => Err(unexpected(c)),
+        }
+    }
+    fn parse_value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace()?;
+        match self.input.next() {
+            Some('{') => self.parse_object(),
+            Some('[') => self.parse_array(),
+            Some('"') => self.parse_string().map(Json::String),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.parse_number(c),
+            Some(c) if c.is_ascii_alphabetic() => self.parse_word(c),
+            c => Err(unexpected(c)),
+        }
+    }
+    fn parse_object(&mut self) -> Result<Json, String> {
+        let mut entries = Vec::new();
+        loop {
+            self.skip_whitespace()?;
+            match self.input.next() {
+                Some('}') => return Ok(Json::Object(entries)),
+                Some('"') => {}
+                c => return Err(unexpected(c)),
+            }
+            let key = self.parse_string()?;
+            self.skip_whitespace()?;
+            match self.input.next() {
+                Some(':') => {}
+                c => return Err(unexpected(c)),
+            }
+            entries.push((key, self.parse_value()?));
+            self.skip_whitespace()?;
+            match self.input.next() {
+                // 末尾のカンマは次のループで } として読む
+                Some(',') => {}
+                Some('}') => return Ok(Json::Object(entries)),
+                c => return Err(unexpected(c)),
+            }
+        }
+    }
+    fn parse_array(&mut self) -> Result<Json, String> {
+        let mut values = Vec::new();
+        loop {
+            self.skip_whitespace()?;
+            if self.input.peek() == Some(&']') {
+                self.input.next();
+                return Ok(Json::Array(values));
+            }
+            values.push(self.parse_value()?);
+            self.skip_whitespace()?;
+            match self.input.next() {
+                Some(',') => {}
+                Some(']') => return Ok(Json::Array(values)),
+                c => return Err(unexpected(c)),
+            }
+        }
+    }
+    // 最初の " は読み終えている
+    fn parse_string(&mut self) -> Result<String, String> {
+        let mut s = String::new();
+        loop {
+            match self.input.next() {
+                Some('"') => return Ok(s),
+                Some('\\') => match self.input.next() {
+                    Some('n') => s.push('\n'),
+                    Some('t') => s.push('\t'),
+                    Some('r') => s.push('\r'),
+                    Some('b') => s.push('\u{8}'),
+                    Some('f') => s.push('\u{c}'),
+                    Some('u') => {
+                        let hex = (0..4).filter_map(|_| self.input.next()).collect::<String>();
+                        let c = u32::from_str_radix(&hex, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| format!("invalid escape `\\u{}`", hex))?;
+                        s.push(c);
+                    }
+                    Some(c) => s.push(c),
+                    None => return Err(unexpected(None)),
+                },
+                Some(c) => s.push(c),
+                None => return Err(unexpected(None)),
+            }
+        }
+    }
+    fn parse_number(&mut self, first: char) -> Result<Json, String> {
+        let mut number = first.to_string();
+        while let Some(&c) = self.input.peek() {
+            if !(c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-')) {
+                break;
+            }
+            number.push(c);
+            self.input.next();
+        }
+        number
+            .parse()
+            .map(Json::Number)
+            .map_err(|_| format!("invalid number `{}`", number))
+    }
+    fn parse_word(&mut self, first: char) -> Result<Json, String> {
+        let mut word = first.to_string();
+        while let Some(&c) = self.input.peek() {
+            if !c.is_ascii_alphabetic() {
+                break;
+            }
+            word.push(c);
+            self.input.next();
+        }
+        match word.as_str() {
+            "true" => Ok(Json::Bool(true)),
+            "false" => Ok(Json::Bool(false)),
+            "null" => Ok(Json::Null),
+            _ => Err(format!("unexpected `{}`", word)),
+        }
+    }
+    // 空白と // , /* */ のコメントを読み飛ばす
+    fn skip_whitespace(&mut self) -> Result<(), String> {
+        while let Some(&c) = self.input.peek() {
+            if c.is_whitespace() {
+                self.input.next();
+                continue;
+            }
+            if c != '/' {
+                break;
+            }
+            self.input.next();
+            match self.input.next() {
+                Some('/') => {
+                    for c in self.input.by_ref() {
+                        if c == '\n' {
+                            break;
+                        }
+                    }
+                }
+                Some('*') => loop {
+                    match self.input.next() {
+                        Some('*') if self.input.peek() == Some(&'/') => {
+                            self.input.next();
+                            break;
+                        }
+                        Some(_) => {}
+                        None => return Err(unexpected(None)),
+                    }
+                },
+                c => return Err(unexpected(c)),
+            }
+        }
+        Ok(())
+    }
+}
+
+fn unexpected(c: Option<char>) -> String {
+    match c {
+        Some(c) => format!("unexpected `{}`", c),
+        None => "unexpected end of file".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::{create_dir_all, create_file, remove_dir};
+
+    #[test]
+    fn test_parse_jsonc() {
+        let source = r#"{
+  // comment
+  "compilerOptions": {
+    /* "baseUrl": "ignored", */
+    "strict": true,
+    "target": "es2017",
+    "lib": ["dom", "esnext",],
+    "paths": { "@/*": ["./src/*"] },
+  },
+  "exclude": null,
+  "version": -1.5e1
+}"#;
+        let json = JsonParser::new(source).parse().unwrap();
+        let options = json.get("compilerOptions").unwrap();
+
+        assert_eq!(options.get("baseUrl"), None);
+        assert_eq!(options.get("strict"), Some(&Json::Bool(true)));
+        assert_eq!(options.get("target").and_then(Json::as_str), Some("es2017"));
+        assert_eq!(
+            options.get("lib").unwrap().as_array(),
+            &[Json::String("dom".into()), Json::String("esnext".into())]
+        );
+        assert_eq!(
+            options.get("paths").unwrap().get("@/*").unwrap().as_array(),
+            &[Json::String("./src/*".into())]
+        );
+        assert_eq!(json.get("exclude"), Some(&Json::Null));
+        assert_eq!(json.get("version"), Some(&Json::Number(-15.0)));
+        assert_eq!(
+            JsonParser::new(r#"{ "a": 1 "b": 2 }"#).parse(),
+            Err("unexpected `\"`".to_string())
+        );
+    }
+    #[test]
+    fn test_tsconfig_extends_paths() {
+        let dir_name = "test_tsconfig_extends_paths";
+        create_dir_all(format!("{dir_name}/config").as_str());
+        create_dir_all(format!("{dir_name}/src/components").as_str());
+        create_file(
+            format!("{dir_name}/config/tsconfig.base.json").as_str(),
+            r#"{
+  "compilerOptions": {
+    // relative to this file
+    "paths": { "@/*": ["../src/*"], "~/types": ["../src/types.ts"] }
+  }
+}"#,
+        );
+        create_file(
+            format!("{dir_name}/tsconfig.json").as_str(),
+            r#"{ "extends": "./config/tsconfig.base", "compilerOptions": { "strict": true } }"#,
+        );
+
+        let config = TsConfig::find(format!("{dir_name}/src/components")).unwrap();
+        remove_dir(dir_name);
+        let config = config.unwrap();
+        let root = absolute(Path::new(dir_name)).unwrap();
+
+        assert_eq!(config.base_url, None);
+        assert_eq!(
+            config.candidates("@/components/Button"),
+            vec![root.join("src/components/Button")]
+        );
+        assert_eq!(
+            config.candidates("~/types"),
+            vec![root.join("src/types.ts")]
+        );
+        assert_eq!(config.candidates("react"), Vec::<PathBuf>::new());
+        assert_eq!(
+            config.module_specifier(format!("{dir_name}/src/components/Button.tsx")),
+            Some("@/components/Button".to_string())
+        );
+        assert_eq!(config.module_specifier("other/Button.tsx"), None);
+    }
+    #[test]
+    fn test_tsconfig_skip_unreadable_extends() {
+        let dir_name = "test_tsconfig_skip_unreadable_extends";
+        create_dir_all(dir_name);
+        create_file(
+            format!("{dir_name}/tsconfig.base.json").as_str(),
+            r#"{ "extends": "./tsconfig.json", "compilerOptions": { "baseUrl": "./lib" } }"#,
+        );
+        create_file(
+            format!("{dir_name}/tsconfig.broken.json").as_str(),
+            r#"{ "compilerOptions": { "baseUrl": } }"#,
+        );
+        create_file(
+            format!("{dir_name}/tsconfig.json").as_str(),
+            r#"{
+  "extends": ["@vue/tsconfig/tsconfig.dom.json", "./tsconfig.base", "./tsconfig.broken"],
+  "compilerOptions": { "paths": { "@/*": ["./src/*"] } }
+}"#,
+        );
+
+        let config = TsConfig::find(dir_name).unwrap();
+        remove_dir(dir_name);
+        let config = config.unwrap();
+        let root = absolute(Path::new(dir_name)).unwrap();
+
+        // 読めた設定だけを使う
+        assert_eq!(config.base_url, Some(root.join("lib")));
+        assert_eq!(
+            config.candidates("@/Button"),
+            vec![root.join("lib/src/Button"), root.join("lib/@/Button")]
+        );
+        let tsconfig = root.join(TSCONFIG);
+        let broken_path = root.join("tsconfig.broken.json");
+        assert_eq!(
+            config.skipped(),
+            &[
+                format!(
+                    "{}: not found extends = @vue/tsconfig/tsconfig.dom.json",
+                    tsconfig.display()
+                ),
+                format!("{}: circular extends", tsconfig.display()),
+                format!("{}: unexpected `}}`", broken_path.display()),
+            ]
+        );
+    }
+    #[test]
+    fn test_tsconfig_find_stops_at_project() {
+        let dir_name = "test_tsconfig_find_stops_at_project";
+        create_dir_all(format!("{dir_name}/app/src").as_str());
+        create_file(format!("{dir_name}/tsconfig.json").as_str(), "{}");
+        create_file(format!("{dir_name}/app/package.json").as_str(), "{}");
+
+        let app = TsConfig::find(format!("{dir_name}/app/src")).unwrap();
+        let root = TsConfig::find(dir_name).unwrap();
+        remove_dir(dir_name);
+
+        // app は別の project なので上の tsconfig.json は使わない
+        assert_eq!(app, None);
+        assert!(root.is_some());
+    }
+    #[test]
+    fn test_tsconfig_base_url() {
+        let dir_name = "test_tsconfig_base_url";
+        create_dir_all(dir_name);
+        create_file(
+            format!("{dir_name}/tsconfig.json").as_str(),
+            r#"{
+  "compilerOptions": {
+    "baseUrl": "./src",
+    "paths": { "@components/*": ["components/*"], "@components/ui/*": ["ui/*"] }
+  }
+}"#,
+        );
+
+        let config = TsConfig::find(dir_name).unwrap();
+        remove_dir(dir_name);
+        let config = config.unwrap();
+        let src = absolute(Path::new(dir_name)).unwrap().join("src");
+
+        assert_eq!(
+            config.candidates("@components/ui/Button"),
+            vec![src.join("ui/Button"), src.join("@components/ui/Button")]
+        );
+        assert_eq!(
+            config.candidates("utils/date"),
+            vec![src.join("utils/date")]
+        );
+        // components/ui/Button is shadowed by @components/ui/*
+        assert_eq!(
+            config.module_specifier(src.join("components/ui/Button.tsx")),
+            None
+        );
+        assert_eq!(
+            config.module_specifier(src.join("components/Card.tsx")),
+            Some("@components/Card".to_string())
+        );
+    }
+}